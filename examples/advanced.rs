@@ -129,7 +129,8 @@ Press T to toggle controls (K and L will still work)"
             // Change the angle of the camera to 35 degrees
             min_angle: 35.0f32.to_radians(),
             // Decrease smoothing
-            smoothness: 0.1,
+            focus_smoothing: 0.1,
+            zoom_smoothing: 0.1,
             // Change starting position
             target_focus: Transform::from_xyz(3.0, 0.0, -3.0),
             // Change starting zoom level
@@ -152,8 +153,10 @@ Press T to toggle controls (K and L will still work)"
             button_drag: Some(MouseButton::Middle),
             // Keep the mouse cursor in place when dragging
             lock_on_drag: true,
-            // Change the width of the area that triggers edge pan. 0.1 is 10% of the window height.
-            edge_pan_width: 0.1,
+            // Change the width of the area that triggers edge pan. 0.1 is 10% of the window width
+            // (for the left and right edges) or height (for the top and bottom edges).
+            edge_pan_width_x: 0.1,
+            edge_pan_width_y: 0.1,
             // Increase pan speed
             pan_speed: 25.0,
             ..default()
@@ -0,0 +1,102 @@
+//! Shows how to place UI over a point in the world, e.g. for health bars or name tags. The UI
+//! must be positioned after the camera has moved, otherwise it will lag behind by a frame.
+
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+
+use bevy_rts_camera::{Ground, RtsCamera, RtsCameraControls, RtsCameraPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(RtsCameraPlugin)
+        .add_systems(Startup, setup)
+        // The camera's `Transform` is updated in `RtsCameraSystemSet` (in `Update`), but
+        // `GlobalTransform` is only updated during transform propagation in `PostUpdate`. Run
+        // after that so the UI uses the camera's position from this frame.
+        .add_systems(
+            PostUpdate,
+            follow_unit.after(TransformSystem::TransformPropagate),
+        )
+        .run();
+}
+
+#[derive(Component)]
+struct Unit;
+
+#[derive(Component)]
+struct Label;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // Ground
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Plane3d::default().mesh().size(80.0, 80.0)),
+            material: materials.add(Color::srgb(0.3, 0.5, 0.3)),
+            ..default()
+        },
+        Ground,
+    ));
+    // A unit to show a label over
+    commands.spawn((
+        PbrBundle {
+            mesh: meshes.add(Capsule3d::new(0.25, 1.25)),
+            material: materials.add(Color::srgb(0.8, 0.7, 0.6)),
+            transform: Transform::from_xyz(0.0, 0.75, 0.0),
+            ..default()
+        },
+        Unit,
+    ));
+    // The label
+    commands.spawn((
+        TextBundle::from_section("Unit", TextStyle::default()).with_style(Style {
+            position_type: PositionType::Absolute,
+            ..default()
+        }),
+        Label,
+    ));
+    // Light
+    commands.spawn(DirectionalLightBundle {
+        directional_light: DirectionalLight {
+            illuminance: 1000.0,
+            shadows_enabled: true,
+            ..default()
+        },
+        transform: Transform::from_rotation(Quat::from_euler(
+            EulerRot::YXZ,
+            150.0f32.to_radians(),
+            -40.0f32.to_radians(),
+            0.0,
+        )),
+        ..default()
+    });
+    // Camera
+    commands.spawn((
+        Camera3dBundle::default(),
+        RtsCamera::default(),
+        RtsCameraControls::default(),
+    ));
+}
+
+// Position the label just above the unit
+fn follow_unit(
+    cam_q: Query<(&RtsCamera, &Camera, &GlobalTransform)>,
+    unit_q: Query<&GlobalTransform, With<Unit>>,
+    mut label_q: Query<&mut Style, With<Label>>,
+) {
+    let (Ok((cam, camera, cam_tfm)), Ok(unit_tfm), Ok(mut style)) = (
+        cam_q.get_single(),
+        unit_q.get_single(),
+        label_q.get_single_mut(),
+    ) else {
+        return;
+    };
+    if let Some(pos) = cam.world_to_screen(camera, cam_tfm, unit_tfm.translation() + Vec3::Y) {
+        style.left = Val::Px(pos.x);
+        style.top = Val::Px(pos.y);
+    }
+}
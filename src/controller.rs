@@ -1,27 +1,74 @@
 #![allow(clippy::too_many_arguments)]
 
-use crate::{Ground, RtsCamera, RtsCameraSystemSet};
+use crate::{
+    ray_to_ground, validate_range, Ground, RtsCamera, RtsCameraActive, RtsCameraEdgePan,
+    RtsCameraMirror, RtsCameraSystemSet,
+};
+use bevy::ecs::entity::{EntityHashMap, EntityHashSet};
+use bevy::ecs::system::SystemParam;
 use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
 use bevy::input::ButtonInput;
 use bevy::prelude::*;
-use bevy::window::{CursorGrabMode, PrimaryWindow};
-use bevy_mod_raycast::immediate::{Raycast, RaycastSettings};
+use bevy::utils::HashMap;
+use bevy::window::{CursorGrabMode, PrimaryWindow, WindowFocused};
+use bevy_mod_raycast::immediate::Raycast;
 use bevy_mod_raycast::prelude::{CursorRay, CursorRayPlugin};
-use std::f32::consts::PI;
+use std::collections::VecDeque;
+use std::f32::consts::{PI, TAU};
 
 pub struct RtsCameraControlsPlugin;
 
 impl Plugin for RtsCameraControlsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(CursorRayPlugin).add_systems(
-            Update,
-            (zoom, pan, grab_pan, rotate).before(RtsCameraSystemSet),
-        );
+        app.add_plugins(CursorRayPlugin)
+            .add_event::<RtsCameraZoomLimit>()
+            .init_resource::<FocusGained>()
+            .init_resource::<ZoomedCameras>()
+            .init_resource::<CursorCamera>()
+            .init_resource::<RtsCameraAllowedActions>()
+            .init_resource::<RtsCameraControlSchemes>()
+            .configure_sets(
+                Update,
+                RtsCameraControlsSystemSet
+                    .before(RtsCameraSystemSet)
+                    .run_if(resource_equals(RtsCameraActive(true))),
+            )
+            .add_systems(PreUpdate, (apply_control_scheme, validate_controls).chain())
+            .add_systems(
+                Update,
+                (
+                    (track_focus, track_cursor_camera, reset_input_state),
+                    (rotate, pan, grab_pan, zoom, touch).chain(),
+                    cursor_lean,
+                )
+                    .chain()
+                    .in_set(RtsCameraControlsSystemSet),
+            );
+
+        #[cfg(feature = "bevy_ui")]
+        app.add_systems(PreUpdate, sync_ui_scale.after(validate_controls));
     }
 }
 
+/// System set containing the systems of `RtsCameraControls`, which update the camera's targets
+/// from input. Runs in `Update`, before `RtsCameraSystemSet`.
+/// Systems that change `target_focus` or `target_zoom` before `RtsCameraSystemSet` are always
+/// picked up the same frame. If you want to override what the controls did this frame (rather
+/// than add to it), run your system after this set as well.
+/// Within the set, input is always handled in the same order: rotating, then panning (keyboard,
+/// edge and drag), then zooming. Rotating first means panning this frame goes in the direction
+/// the camera is now facing, and zooming last means zoom anchors (see `ZoomAnchor`) are found
+/// where the camera has panned to, so holding several inputs at once feels the same every frame.
+#[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
+pub struct RtsCameraControlsSystemSet;
+
 /// Optional camera controller. If you want to use an input manager, don't use this and instead
 /// control the camera yourself by updating `RtsCamera.target_focus` and `RtsCamera.target_zoom`.
+/// Where the camera is heading for (its targets) is kept on `RtsCamera`, so these controls can be
+/// removed and re-added (e.g. by a settings menu) without the camera losing its position. Transient
+/// input state (an active drag or rotation, rotate speed smoothing, edge pan timers) isn't kept,
+/// and starts over once they're re-added. If they're removed while rotating or drag panning, the
+/// cursor is unlocked.
 /// # Example
 /// ```no_run
 /// # use bevy::prelude::*;
@@ -42,7 +89,8 @@ impl Plugin for RtsCameraControlsPlugin {
 ///         ));
 ///  }
 /// ```
-#[derive(Component, Debug, PartialEq, Clone)]
+#[derive(Component, Debug, PartialEq, Clone, Reflect)]
+#[reflect(Component)]
 pub struct RtsCameraControls {
     /// The key that will pan the camera up (or forward).
     /// Defaults to `KeyCode::ArrowUp`.
@@ -56,9 +104,36 @@ pub struct RtsCameraControls {
     /// The key that will pan the camera right.
     /// Defaults to `KeyCode::ArrowRight`.
     pub key_right: KeyCode,
-    /// The mouse button used to rotate the camera.
+    /// Another key that will pan the camera up (or forward), e.g. `KeyCode::KeyW` so that both
+    /// WASD and the arrow keys pan. Holding two neighbouring pan keys pans diagonally at the same
+    /// speed, so the keyboard alone can pan in 8 directions (e.g. with edge panning disabled on
+    /// devices without a mouse).
+    /// Defaults to `None`.
+    pub key_pan_up: Option<KeyCode>,
+    /// Another key that will pan the camera down (or backward). See `key_pan_up`.
+    /// Defaults to `None`.
+    pub key_pan_down: Option<KeyCode>,
+    /// Another key that will pan the camera left. See `key_pan_up`.
+    /// Defaults to `None`.
+    pub key_pan_left: Option<KeyCode>,
+    /// Another key that will pan the camera right. See `key_pan_up`.
+    /// Defaults to `None`.
+    pub key_pan_right: Option<KeyCode>,
+    /// The mouse button used to rotate the camera. This can be changed at any time, e.g. from a
+    /// settings menu. If it changes while rotating, rotating stops until the new button is pressed.
+    /// Moving the mouse across the camera's whole viewport rotates half a turn. If the camera has
+    /// no viewport and there isn't exactly one primary window to measure instead (e.g. in some
+    /// multi-window setups), a width of 1280 logical pixels is used.
     /// Defaults to `MouseButton::Middle`.
     pub button_rotate: MouseButton,
+    /// Another mouse button that can also be used to rotate the camera, e.g. so either middle or
+    /// right mouse rotates.
+    /// Defaults to `None`.
+    pub button_rotate_alt: Option<MouseButton>,
+    /// A key that, while held, makes mouse movement rotate the camera (like holding
+    /// `button_rotate`). Useful for laptops without a middle mouse button.
+    /// Defaults to `None`.
+    pub key_rotate_modifier: Option<KeyCode>,
     /// The key that will rotate the camera left.
     /// Defaults to `KeyCode::KeyQ`.
     pub key_rotate_left: KeyCode,
@@ -68,28 +143,277 @@ pub struct RtsCameraControls {
     /// How fast the keys will rotate the camera.
     /// Defaults to `16.0`.
     pub key_rotate_speed: f32,
+    /// The key that will pitch the camera up (towards the horizon), by raising
+    /// `RtsCamera::min_angle`. The angle still increases as you zoom in if `dynamic_angle` is
+    /// enabled, and is kept below `RtsCamera::max_angle`.
+    /// Defaults to `None`.
+    pub key_pitch_up: Option<KeyCode>,
+    /// The key that will pitch the camera down (towards looking straight down), by lowering
+    /// `RtsCamera::min_angle`.
+    /// Defaults to `None`.
+    pub key_pitch_down: Option<KeyCode>,
+    /// How fast the pitch keys change the camera angle, in radians per second.
+    /// Defaults to `TAU / 8.0` (45 degrees per second).
+    pub key_pitch_speed: f32,
+    /// The key that will turn the camera back to face north (-Z), without moving or zooming it.
+    /// If `key_pitch_up` or `key_pitch_down` are set, the angle is also reset to what `min_angle`
+    /// was when these controls first drove the camera. The camera smoothly rotates back.
+    /// Defaults to `None`.
+    pub key_reset_rotation: Option<KeyCode>,
+    /// Rotation sensitivity multiplier when fully zoomed in (`target_zoom` of `1.0`). The actual
+    /// multiplier is interpolated between `rotation_zoom_scale_far` and this based on zoom.
+    /// Defaults to `1.0`.
+    pub rotation_zoom_scale_near: f32,
+    /// Rotation sensitivity multiplier when fully zoomed out (`target_zoom` of `0.0`). Set this
+    /// lower than `rotation_zoom_scale_near` to make rotation less sensitive when zoomed out.
+    /// Defaults to `1.0`.
+    pub rotation_zoom_scale_far: f32,
+    /// The minimum `target_zoom` at which the camera can be rotated. Rotation input is ignored
+    /// when zoomed out further than this, which avoids disorienting rotation when viewing the
+    /// whole map.
+    /// Defaults to `0.0` (can always rotate).
+    pub rotate_min_zoom: f32,
     /// Whether to lock the mouse cursor in place while rotating.
     /// Defaults to `false`.
     pub lock_on_rotate: bool,
+    /// Whether mouse rotation should be based on the total distance the mouse has moved since
+    /// `button_rotate` was pressed, relative to the heading at that moment, instead of adding up
+    /// the movement each frame. This avoids drift, so dragging across the whole viewport and back
+    /// always returns to exactly where you started.
+    /// Defaults to `false`.
+    pub rotate_absolute_drag: bool,
+    /// How much to smooth the mouse movement used for rotation, between `0.0` and `1.0`. Some mice
+    /// report noisy, tiny movements that make slow rotation jittery, which this filters out. This
+    /// is independent of the frame rate, and works like `RtsCamera::focus_smoothing` (but on the
+    /// speed of the mouse rather than the camera), so low values keep fast drags responsive.
+    /// Doesn't apply to `rotate_absolute_drag`.
+    /// Defaults to `0.0` (no smoothing).
+    pub rotate_input_smoothing: f32,
     /// The mouse button used to 'drag pan' the camera.
     /// Defaults to `None`.
     pub button_drag: Option<MouseButton>,
+    /// Whether drag panning should suppress keyboard panning. Edge panning is always suppressed
+    /// while dragging, but by default keyboard panning is added on top of the drag.
+    /// Defaults to `false`.
+    pub drag_exclusive: bool,
     /// Whether to lock the mouse cursor in place while dragging.
     /// Defaults to `false`.
     pub lock_on_drag: bool,
-    /// How far away from the side of the screen edge pan will kick in, defined as a percentage
-    /// of the window's height. Set to `0.0` to disable edge panning.
+    /// Whether to apply smoothing while drag panning. When disabled, the ground will stick to the
+    /// cursor exactly while dragging.
+    /// Defaults to `false`.
+    pub smooth_drag: bool,
+    /// How far in logical pixels the mouse has to move while `button_drag` is held before drag
+    /// panning starts, so a click (e.g. to select a unit) doesn't nudge the map. The cursor is
+    /// only locked (see `lock_on_drag`) once drag panning starts.
+    /// Defaults to `0.0`.
+    pub drag_threshold: f32,
+    /// Whether to ignore zoom input while drag panning, so a stray scroll doesn't disturb the drag.
+    /// Defaults to `false`.
+    pub drag_locks_zoom: bool,
+    /// Whether to ignore rotation input while drag panning, so a stray button press doesn't
+    /// disturb the drag.
+    /// Defaults to `false`.
+    pub drag_locks_rotate: bool,
+    /// How far away from the left and right sides of the camera's viewport edge pan will kick in,
+    /// defined as a percentage of the viewport's width (or in logical pixels, see
+    /// `edge_pan_width_unit`). Set to `0.0` to disable horizontal edge panning.
+    /// Defaults to `0.05` (5%).
+    pub edge_pan_width_x: f32,
+    /// How far away from the top and bottom sides of the camera's viewport edge pan will kick in,
+    /// defined as a percentage of the viewport's height (or in logical pixels, see
+    /// `edge_pan_width_unit`). Set to `0.0` to disable vertical edge panning.
     /// Defaults to `0.05` (5%).
-    pub edge_pan_width: f32,
-    /// Speed of camera pan (either via keyboard controls or edge panning).
+    pub edge_pan_width_y: f32,
+    /// What `edge_pan_width_x` and `edge_pan_width_y` are measured in.
+    /// Defaults to `EdgePanWidthUnit::Fraction`.
+    pub edge_pan_width_unit: EdgePanWidthUnit,
+    /// Multiplier for `edge_pan_width_x` and `edge_pan_width_y` when fully zoomed in
+    /// (`target_zoom` of `1.0`). The actual multiplier is interpolated between
+    /// `edge_pan_width_far` and this based on zoom.
+    /// Defaults to `1.0`.
+    pub edge_pan_width_near: f32,
+    /// Multiplier for `edge_pan_width_x` and `edge_pan_width_y` when fully zoomed out
+    /// (`target_zoom` of `0.0`).
+    /// Defaults to `1.0`.
+    pub edge_pan_width_far: f32,
+    /// Whether edge panning should get faster the closer the cursor is to the edge of the screen,
+    /// starting from nothing at the inner edge of the edge pan zone. When disabled, edge panning
+    /// is always at full speed.
+    /// Defaults to `false`.
+    pub edge_pan_ramp: bool,
+    /// Whether edge panning still works while `button_rotate` (or `button_rotate_alt`) is held, so you can pan and rotate
+    /// at the same time.
+    /// Defaults to `false`.
+    pub edge_pan_during_rotate: bool,
+    /// Whether to keep edge panning in the last direction when the cursor leaves the window (or
+    /// the camera's viewport), e.g. if you overshoot the edge in windowed mode. When disabled,
+    /// edge panning stops.
+    /// Defaults to `false`.
+    pub edge_pan_continue_outside: bool,
+    /// Whether to keep edge panning while the cursor is hidden or grabbed by something else (e.g.
+    /// another game mode), which can stop the window reporting the cursor position. Instead, a
+    /// virtual cursor starts from the last known position and is moved by mouse motion, staying
+    /// inside the window. It isn't moved while these controls rotate or drag pan (which grab the
+    /// cursor themselves), so it's back where it was once they let go.
+    /// Defaults to `false`.
+    pub edge_pan_virtual_cursor: bool,
+    /// Whether edge panning should move the map towards the edge of the screen the cursor is at,
+    /// instead of moving the camera towards it. Some players with motor difficulties find this
+    /// easier to use.
+    /// Defaults to `false`.
+    pub edge_pan_invert: bool,
+    /// How long in seconds the cursor has to stay in the edge pan zone before edge panning
+    /// starts. This avoids accidental panning when quickly moving the cursor past the edge, or
+    /// clicking UI near it.
+    /// Defaults to `0.0` (pan immediately).
+    pub edge_pan_activation_delay: f32,
+    /// How long in seconds edge panning takes to speed up to full speed after the cursor enters
+    /// the edge pan zone, and to slow down to a stop after it leaves. This avoids a jolt when
+    /// crossing into the zone.
+    /// Defaults to `0.0` (start and stop immediately).
+    pub edge_pan_ramp_time: f32,
+    /// How far in logical pixels the cursor has to move back out of the edge pan zone before
+    /// edge panning stops. This stops panning flickering on and off when the cursor jitters right
+    /// at the edge of the zone, e.g. on high DPI displays. Has little effect with
+    /// `edge_pan_ramp`, which already slows down to nothing at the edge of the zone.
+    /// Defaults to `0.0`.
+    pub edge_pan_hysteresis: f32,
+    /// How much bigger pixel-based inputs should be, for apps that scale their UI (and so expect
+    /// pixel sizes to scale with it). Edge pan widths measured in `EdgePanWidthUnit::Pixels`,
+    /// `edge_pan_hysteresis` and `drag_threshold` are multiplied by this, and mouse wheel input
+    /// measured in pixels (e.g. from touchpads) is divided by it. Inputs measured in lines or as a
+    /// fraction of the window are not affected.
+    /// With the `bevy_ui` feature, this is updated automatically from Bevy's `UiScale` resource
+    /// whenever it exists.
+    /// Defaults to `1.0`.
+    pub ui_scale: f32,
+    /// Whether to stop edge panning for a moment (0.3 seconds) after each mouse wheel zoom, so
+    /// scrolling with the cursor near the edge of the screen doesn't also pan. Only scrolling that
+    /// actually zoomed this camera counts, so scrolling over another camera's viewport or while
+    /// zooming isn't allowed doesn't stop it. Keyboard panning is unaffected.
+    /// Defaults to `false`.
+    pub suppress_pan_while_zooming: bool,
+    /// How far to shift the view towards the ground under the cursor, as a fraction of the
+    /// distance from the focus to it, e.g. `0.1` leans a tenth of the way there. The further the
+    /// cursor is from the center of the screen, the further the view leans, and it moves back when
+    /// the cursor does. Keep this small for a subtle effect. Not applied while drag panning. The
+    /// lean is kept in `RtsCamera.lean` rather than moving the focus, so `bounds` don't limit it.
+    /// Clamped between `0.0` and `0.5`.
+    /// Defaults to `0.0` (disabled).
+    pub cursor_lean: f32,
+    /// Speed of camera pan (either via keyboard controls or edge panning). When panning with
+    /// several at once (including `RtsCameraPanIntent`), their directions are combined, and the
+    /// camera never pans faster than this (scaled by zoom, see `pan_speed_mode`), e.g. holding up
+    /// and right with the cursor in the top right corner pans diagonally at exactly this speed.
     /// Defaults to `15.0`.
     pub pan_speed: f32,
+    /// What `pan_speed` is measured in.
+    /// Defaults to `PanSpeedUnit::WorldUnits`.
+    pub pan_speed_unit: PanSpeedUnit,
+    /// How `pan_speed` changes as the camera zooms. Only applies to `PanSpeedUnit::WorldUnits`.
+    /// Defaults to `PanSpeedMode::Fixed`.
+    pub pan_speed_mode: PanSpeedMode,
+    /// Whether `PanSpeedMode::Fixed` slows panning down as you zoom in. When disabled, the camera
+    /// always pans at exactly `pan_speed`.
+    /// Defaults to `true`.
+    pub pan_speed_scales_with_zoom: bool,
+    /// How much faster to pan when zoomed out, on top of `pan_speed_mode`, for quickly crossing
+    /// large maps. The pan speed is multiplied by `1.0 + pan_zoom_boost` when fully zoomed out,
+    /// going down to no boost when fully zoomed in, e.g. `1.0` pans twice as fast when zoomed out.
+    /// Defaults to `0.0`.
+    pub pan_zoom_boost: f32,
+    /// Whether the pan keys should orbit around the focus instead of moving it, like a turntable.
+    /// Left/right will rotate the camera, and up/down will change the angle (`min_angle`).
+    /// Edge panning is not affected.
+    /// Defaults to `false`.
+    pub orbit_mode: bool,
+    /// Whether the pan keys should always pan along the world axes, no matter which way the camera
+    /// is facing. `key_up` pans towards -Z (north, the way the camera faces at a yaw of `0.0`), and
+    /// `key_right` towards +X. Useful for grid based games. Edge panning is not affected.
+    /// Defaults to `false`.
+    pub pan_fixed_compass: bool,
+    /// Whether keyboard and edge panning should move the map the way they point, rather than the
+    /// camera, like 'grabbing' the world. E.g. pressing `key_up` moves the map up the screen, which
+    /// moves the camera back. Drag panning and orbiting (see `orbit_mode`) are not affected.
+    /// This combines with `edge_pan_invert`, so enabling both makes edge panning move the camera
+    /// again.
+    /// Defaults to `false`.
+    pub invert_pan: bool,
+    /// How fast the pan keys orbit the camera when `orbit_mode` is enabled, in radians per second.
+    /// Defaults to `TAU / 4.0` (90 degrees per second).
+    pub orbit_speed: f32,
     /// How much the camera will zoom.
     /// Defaults to `1.0`.
     pub zoom_sensitivity: f32,
+    /// Key that, while held, makes the mouse wheel zoom in finer steps (see `zoom_fine_factor`),
+    /// e.g. for precisely framing cinematics or screenshots.
+    /// Defaults to `None`.
+    pub key_zoom_fine: Option<KeyCode>,
+    /// How much each mouse wheel step is multiplied by while `key_zoom_fine` is held.
+    /// Defaults to `0.2`.
+    pub zoom_fine_factor: f32,
+    /// How much harder it is to zoom in over the last stretch before `RtsCamera::zoom_max`, from
+    /// `0.0` (a hard stop at the limit) to `1.0`. Zooming in slows down the closer the camera gets
+    /// to the limit, so it doesn't slam into the ground or units but still allows close
+    /// inspection. At `1.0`, the limit is approached but never quite reached.
+    /// Defaults to `0.0`.
+    pub zoom_near_resistance: f32,
+    /// How long in seconds each frame's mouse wheel input is spread out over. Fast scroll wheels
+    /// can send a burst of scrolling in a single frame, which makes the zoom jump. Spreading it out
+    /// makes rapid scrolling continuous, while a single notch still starts zooming straight away.
+    /// Defaults to `0.0` (applied immediately).
+    pub zoom_input_smoothing: f32,
+    /// Whether to spread out only the part of each frame's mouse wheel input beyond a single
+    /// notch, so precise single notches are always applied immediately, while bursts from fast
+    /// scrolling are spread over `zoom_input_smoothing` (or 0.1 seconds if that is `0.0`).
+    /// Defaults to `false`.
+    pub zoom_event_spread: bool,
+    /// Which point should stay in place on screen while zooming.
+    /// Defaults to `ZoomAnchor::Focus`.
+    pub zoom_anchor: ZoomAnchor,
+    /// What to zoom towards when `zoom_anchor` is `ZoomAnchor::Cursor` but the cursor isn't over
+    /// the ground (e.g. pointing at the sky).
+    /// Defaults to `ZoomCursorMiss::FocusCentered`.
+    pub zoom_cursor_miss: ZoomCursorMiss,
+    /// How strongly zooming with `ZoomAnchor::Cursor` moves the focus towards the cursor, from
+    /// `0.0` (like `ZoomAnchor::Focus`) to `1.0` (the ground under the cursor stays exactly in
+    /// place). Values in between lean towards the cursor by that proportion of each step, for a
+    /// subtler feel on big zoom steps.
+    /// Defaults to `1.0`.
+    pub zoom_cursor_strength: f32,
+    /// What the mouse wheel does while a rotate button (or `key_rotate_modifier`) is held.
+    /// Defaults to `ZoomWhileRotating::Normal`.
+    pub zoom_while_rotating: ZoomWhileRotating,
+    /// Whether to ignore mouse motion and scrolling on the frame a window regains focus. This
+    /// prevents the camera from jumping when alt-tabbing back into the game. For rotating, the
+    /// first mouse motion after regaining focus is ignored instead, as some platforms send a
+    /// large synthetic motion a frame or more after the focus event.
+    /// Defaults to `true`.
+    pub ignore_input_on_focus: bool,
+    /// Whether two-finger touch gestures are enabled. Moving two fingers will pan, pinching will
+    /// zoom, and twisting will rotate, all at the same time. Pinching zooms like the mouse wheel
+    /// does, using `zoom_anchor` (with the point between the fingers as the cursor). With several
+    /// cameras, only the one under the point between the fingers is affected (the highest `order`
+    /// if their viewports overlap), like the mouse.
+    /// Defaults to `true`.
+    pub touch: bool,
+    /// The camera entity (with `RtsCamera`) that these controls should drive. Set this if the
+    /// controls live on a different entity to the camera. If `None`, the controls drive the
+    /// camera on the same entity.
+    /// Not included when reflecting, as entities can't be saved.
+    /// Defaults to `None`.
+    #[reflect(ignore)]
+    pub camera: Option<Entity>,
     /// Whether these controls are enabled.
     /// Defaults to `true`.
     pub enabled: bool,
+    /// The profiles replaced by `push_override`, with the most recent last. Use `push_override`
+    /// and `pop_override` rather than changing this directly.
+    /// Not included when reflecting.
+    /// Defaults to empty.
+    #[reflect(ignore)]
+    pub override_stack: Vec<RtsCameraControlProfile>,
 }
 
 impl Default for RtsCameraControls {
@@ -99,233 +423,2145 @@ impl Default for RtsCameraControls {
             key_down: KeyCode::ArrowDown,
             key_left: KeyCode::ArrowLeft,
             key_right: KeyCode::ArrowRight,
+            key_pan_up: None,
+            key_pan_down: None,
+            key_pan_left: None,
+            key_pan_right: None,
             button_rotate: MouseButton::Middle,
+            button_rotate_alt: None,
+            key_rotate_modifier: None,
             key_rotate_left: KeyCode::KeyQ,
             key_rotate_right: KeyCode::KeyE,
             key_rotate_speed: 16.0,
+            key_pitch_up: None,
+            key_pitch_down: None,
+            key_pitch_speed: TAU / 8.0,
+            key_reset_rotation: None,
+            rotation_zoom_scale_near: 1.0,
+            rotation_zoom_scale_far: 1.0,
+            rotate_min_zoom: 0.0,
             lock_on_rotate: false,
+            rotate_absolute_drag: false,
+            rotate_input_smoothing: 0.0,
             button_drag: None,
+            drag_exclusive: false,
             lock_on_drag: false,
-            edge_pan_width: 0.05,
+            smooth_drag: false,
+            drag_threshold: 0.0,
+            drag_locks_zoom: false,
+            drag_locks_rotate: false,
+            edge_pan_width_x: 0.05,
+            edge_pan_width_y: 0.05,
+            edge_pan_width_unit: EdgePanWidthUnit::Fraction,
+            edge_pan_width_near: 1.0,
+            edge_pan_width_far: 1.0,
+            edge_pan_ramp: false,
+            edge_pan_during_rotate: false,
+            edge_pan_continue_outside: false,
+            edge_pan_virtual_cursor: false,
+            edge_pan_invert: false,
+            edge_pan_activation_delay: 0.0,
+            edge_pan_ramp_time: 0.0,
+            edge_pan_hysteresis: 0.0,
+            ui_scale: 1.0,
+            suppress_pan_while_zooming: false,
+            cursor_lean: 0.0,
             pan_speed: 15.0,
+            pan_speed_unit: PanSpeedUnit::WorldUnits,
+            pan_speed_mode: PanSpeedMode::Fixed,
+            pan_speed_scales_with_zoom: true,
+            pan_zoom_boost: 0.0,
+            orbit_mode: false,
+            pan_fixed_compass: false,
+            invert_pan: false,
+            orbit_speed: TAU / 4.0,
             zoom_sensitivity: 1.0,
+            key_zoom_fine: None,
+            zoom_fine_factor: 0.2,
+            zoom_near_resistance: 0.0,
+            zoom_input_smoothing: 0.0,
+            zoom_event_spread: false,
+            zoom_anchor: ZoomAnchor::Focus,
+            zoom_cursor_miss: ZoomCursorMiss::FocusCentered,
+            zoom_cursor_strength: 1.0,
+            zoom_while_rotating: ZoomWhileRotating::Normal,
+            ignore_input_on_focus: true,
+            touch: true,
+            camera: None,
             enabled: true,
+            override_stack: Vec::new(),
+        }
+    }
+}
+
+/// Event sent by `RtsCameraControls` when zooming reaches or pushes against the zoom limits, i.e.
+/// further scrolling in that direction won't do anything. Useful for e.g. disabling zoom buttons
+/// in your UI.
+#[derive(Event, Copy, Clone, Debug, PartialEq)]
+pub struct RtsCameraZoomLimit {
+    /// The camera entity that hit the limit.
+    pub entity: Entity,
+    /// Whether the camera is fully zoomed out (`target_zoom` is `zoom_min`).
+    pub at_min: bool,
+    /// Whether the camera is fully zoomed in (`target_zoom` is `zoom_max`).
+    pub at_max: bool,
+}
+
+/// Marks an `RtsCamera` that the built-in controls should skip entirely, e.g. cameras rendering
+/// to an off-screen texture. These are filtered out of the controls' queries, so with many cameras
+/// (e.g. one per split-screen player plus several render targets) only the cameras that need
+/// input pay for it. Unlike `RtsCameraControls::enabled`, this is per camera, so it also works
+/// when the controls live on another entity.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RtsCameraInputDisabled;
+
+/// Query filter for the cameras the built-in controls can drive.
+type ControllableCamera = (Without<RtsCameraMirror>, Without<RtsCameraInputDisabled>);
+
+/// Query data for where the controls report what they're doing, if the camera has one.
+type InputState = Option<&'static mut RtsCameraInputState>;
+
+/// Query data for the cameras `pan` drives.
+type PanCamera = (
+    &'static mut RtsCamera,
+    Option<&'static Camera>,
+    Option<&'static mut RtsCameraPanIntent>,
+    Option<&'static Projection>,
+    InputState,
+);
+
+/// Resource that controls which actions the built-in controls can perform, for all cameras. This
+/// is finer grained than `RtsCameraControls::enabled`, e.g. to stop panning while placing a
+/// building but still allow zooming.
+/// Defaults to allowing everything.
+#[derive(Resource, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RtsCameraAllowedActions {
+    /// Whether the controls can pan (including drag panning and edge panning).
+    pub allow_pan: bool,
+    /// Whether the controls can zoom.
+    pub allow_zoom: bool,
+    /// Whether the controls can rotate (including changing the angle).
+    pub allow_rotate: bool,
+}
+
+impl Default for RtsCameraAllowedActions {
+    fn default() -> Self {
+        RtsCameraAllowedActions {
+            allow_pan: true,
+            allow_zoom: true,
+            allow_rotate: true,
+        }
+    }
+}
+
+/// The primary window, as the controls use it: its size, where the cursor is in it, and locking
+/// the cursor while rotating or drag panning.
+#[derive(SystemParam)]
+pub(crate) struct ControlsWindow<'w, 's> {
+    window_q: Query<'w, 's, &'static mut Window, With<PrimaryWindow>>,
+    // The grab mode from before the cursor was locked, to go back to once it's unlocked
+    previous_grab_mode: Local<'s, CursorGrabMode>,
+}
+
+impl ControlsWindow<'_, '_> {
+    /// The size of the window in logical pixels, or `None` if there isn't exactly one primary
+    /// window.
+    fn size(&self) -> Option<Vec2> {
+        self.window_q.get_single().ok().map(|window| window.size())
+    }
+
+    /// The cursor position in logical pixels, or `None` if it isn't in the window. Some platforms
+    /// keep reporting the last known position after the cursor leaves the window, so this checks
+    /// it's actually inside.
+    fn cursor_position(&self) -> Option<Vec2> {
+        let window = self.window_q.get_single().ok()?;
+        let size = window.size();
+        window
+            .cursor_position()
+            .filter(|pos| (0.0..=size.x).contains(&pos.x) && (0.0..=size.y).contains(&pos.y))
+    }
+
+    /// Whether the cursor is hidden or grabbed, e.g. by the controls or another system.
+    fn cursor_hidden(&self) -> bool {
+        self.window_q.get_single().is_ok_and(|window| {
+            !window.cursor.visible || window.cursor.grab_mode != CursorGrabMode::None
+        })
+    }
+
+    /// The size of `camera`'s viewport in logical pixels, or of the window if it doesn't have one.
+    fn view_size(&self, camera: &Camera) -> Option<Vec2> {
+        camera.logical_viewport_size().or_else(|| self.size())
+    }
+
+    /// Locks and hides the cursor until `unlock_cursor`. Returns whether there was a window to
+    /// lock it in.
+    fn lock_cursor(&mut self) -> bool {
+        let Ok(mut window) = self.window_q.get_single_mut() else {
+            return false;
+        };
+        *self.previous_grab_mode = window.cursor.grab_mode;
+        window.cursor.grab_mode = CursorGrabMode::Locked;
+        window.cursor.visible = false;
+        true
+    }
+
+    /// Shows the cursor again, and restores how it was grabbed before `lock_cursor`.
+    fn unlock_cursor(&mut self) {
+        if let Ok(mut window) = self.window_q.get_single_mut() {
+            window.cursor.grab_mode = *self.previous_grab_mode;
+            window.cursor.visible = true;
+        }
+    }
+}
+
+/// Whether a window regained focus this frame.
+#[derive(Resource, Default)]
+pub(crate) struct FocusGained(bool);
+
+/// The cameras the controls zoomed during the last run of `zoom`, for
+/// `RtsCameraControls::suppress_pan_while_zooming`.
+#[derive(Resource, Default)]
+pub(crate) struct ZoomedCameras(EntityHashSet);
+
+fn track_focus(
+    mut focus_events: EventReader<WindowFocused>,
+    mut focus_gained: ResMut<FocusGained>,
+) {
+    focus_gained.0 = focus_events.read().any(|event| event.focused);
+}
+
+/// The camera under the cursor, which is the only one that responds to mouse input. When several
+/// cameras' viewports contain the cursor (e.g. overlapping viewports, or cameras without a
+/// viewport), the one with the highest `Camera::order` wins. `None` if the cursor isn't over any
+/// camera, in which case every camera responds as normal.
+#[derive(Resource, Default)]
+pub(crate) struct CursorCamera(Option<Entity>);
+
+impl CursorCamera {
+    /// Whether the camera should respond to mouse input.
+    fn claims(&self, cam_entity: Entity) -> bool {
+        self.0.is_none() || self.0 == Some(cam_entity)
+    }
+}
+
+fn track_cursor_camera(
+    controls_q: Query<(Entity, &RtsCameraControls)>,
+    cam_q: Query<(Entity, &Camera)>,
+    window: ControlsWindow,
+    mut cursor_camera: ResMut<CursorCamera>,
+) {
+    cursor_camera.0 = window.cursor_position().and_then(|cursor| {
+        topmost_camera_at(
+            cursor,
+            controls_q
+                .iter()
+                .filter(|(_, ctrl)| ctrl.enabled)
+                .filter_map(|(entity, ctrl)| cam_q.get(ctrl.camera.unwrap_or(entity)).ok())
+                .filter(|(_, camera)| camera.is_active)
+                .map(|(entity, camera)| (entity, camera.order, camera.logical_viewport_rect())),
+        )
+    });
+}
+
+/// The camera that input at `point` (in logical pixels) goes to, out of `cameras` given as their
+/// entity, `order` and logical viewport rect: the one with the highest `order` whose viewport
+/// contains it. Cameras without a viewport rect never contain it.
+fn topmost_camera_at(
+    point: Vec2,
+    cameras: impl IntoIterator<Item = (Entity, isize, Option<Rect>)>,
+) -> Option<Entity> {
+    cameras
+        .into_iter()
+        .filter(|(_, _, rect)| rect.is_some_and(|rect| rect.contains(point)))
+        // Entity breaks ties between cameras with the same order, so the choice is stable
+        .max_by_key(|(entity, order, _)| (*order, *entity))
+        .map(|(entity, _, _)| entity)
+}
+
+/// The speed and sensitivity settings of `RtsCameraControls`, without the key bindings. Useful for
+/// switching between different camera 'feels', e.g. a build mode and a combat mode.
+/// This covers every numeric setting except `ui_scale` (which follows `UiScale`), plus
+/// `edge_pan_width_unit` as the edge pan widths are in that unit. Bindings, on/off toggles and the
+/// other modes (e.g. `pan_speed_mode`) are left as they are.
+/// # Example
+/// ```
+/// # use bevy_rts_camera::{RtsCameraControlProfile, RtsCameraControls};
+/// let build_mode = RtsCameraControlProfile {
+///     pan_speed: 5.0,
+///     ..Default::default()
+/// };
+/// let mut controls = RtsCameraControls::default();
+/// controls.apply_profile(&build_mode);
+/// assert_eq!(controls.pan_speed, 5.0);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RtsCameraControlProfile {
+    /// See `RtsCameraControls.key_rotate_speed`.
+    pub key_rotate_speed: f32,
+    /// See `RtsCameraControls.key_pitch_speed`.
+    pub key_pitch_speed: f32,
+    /// See `RtsCameraControls.rotation_zoom_scale_near`.
+    pub rotation_zoom_scale_near: f32,
+    /// See `RtsCameraControls.rotation_zoom_scale_far`.
+    pub rotation_zoom_scale_far: f32,
+    /// See `RtsCameraControls.rotate_min_zoom`.
+    pub rotate_min_zoom: f32,
+    /// See `RtsCameraControls.rotate_input_smoothing`.
+    pub rotate_input_smoothing: f32,
+    /// See `RtsCameraControls.drag_threshold`.
+    pub drag_threshold: f32,
+    /// See `RtsCameraControls.edge_pan_width_x`.
+    pub edge_pan_width_x: f32,
+    /// See `RtsCameraControls.edge_pan_width_y`.
+    pub edge_pan_width_y: f32,
+    /// See `RtsCameraControls.edge_pan_width_unit`.
+    pub edge_pan_width_unit: EdgePanWidthUnit,
+    /// See `RtsCameraControls.edge_pan_width_near`.
+    pub edge_pan_width_near: f32,
+    /// See `RtsCameraControls.edge_pan_width_far`.
+    pub edge_pan_width_far: f32,
+    /// See `RtsCameraControls.edge_pan_activation_delay`.
+    pub edge_pan_activation_delay: f32,
+    /// See `RtsCameraControls.edge_pan_ramp_time`.
+    pub edge_pan_ramp_time: f32,
+    /// See `RtsCameraControls.edge_pan_hysteresis`.
+    pub edge_pan_hysteresis: f32,
+    /// See `RtsCameraControls.cursor_lean`.
+    pub cursor_lean: f32,
+    /// See `RtsCameraControls.pan_speed`.
+    pub pan_speed: f32,
+    /// See `RtsCameraControls.pan_zoom_boost`.
+    pub pan_zoom_boost: f32,
+    /// See `RtsCameraControls.orbit_speed`.
+    pub orbit_speed: f32,
+    /// See `RtsCameraControls.zoom_sensitivity`.
+    pub zoom_sensitivity: f32,
+    /// See `RtsCameraControls.zoom_fine_factor`.
+    pub zoom_fine_factor: f32,
+    /// See `RtsCameraControls.zoom_near_resistance`.
+    pub zoom_near_resistance: f32,
+    /// See `RtsCameraControls.zoom_input_smoothing`.
+    pub zoom_input_smoothing: f32,
+    /// See `RtsCameraControls.zoom_cursor_strength`.
+    pub zoom_cursor_strength: f32,
+}
+
+impl Default for RtsCameraControlProfile {
+    fn default() -> Self {
+        RtsCameraControls::default().profile()
+    }
+}
+
+impl From<RtsCameraControlProfile> for RtsCameraControls {
+    fn from(profile: RtsCameraControlProfile) -> Self {
+        let mut controls = RtsCameraControls::default();
+        controls.apply_profile(&profile);
+        controls
+    }
+}
+
+/// Resource of named control profiles that the player can pick between, e.g. "Classic",
+/// "Modern" and "Accessibility" presets in a settings menu. The active scheme is applied to all
+/// `RtsCameraControls` whenever it changes, and to controls as they're added. While overrides
+/// are pushed (see `RtsCameraControls::push_override`), it replaces the settings that
+/// `pop_override` goes back to, rather than the override itself.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_rts_camera::{RtsCameraControlProfile, RtsCameraControlSchemes};
+/// fn pick_scheme(mut schemes: ResMut<RtsCameraControlSchemes>) {
+///     schemes.insert(
+///         "Accessibility",
+///         RtsCameraControlProfile {
+///             pan_speed: 8.0,
+///             ..Default::default()
+///         },
+///     );
+///     schemes.select("Accessibility");
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct RtsCameraControlSchemes {
+    /// The available schemes, by name.
+    pub schemes: HashMap<String, RtsCameraControlProfile>,
+    /// The name of the scheme to apply, or `None` to leave the controls' settings alone. Nothing
+    /// is applied if there's no scheme with this name.
+    /// Defaults to `None`.
+    pub active: Option<String>,
+}
+
+impl RtsCameraControlSchemes {
+    /// Adds a scheme called `name`, replacing any existing scheme with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, profile: RtsCameraControlProfile) {
+        self.schemes.insert(name.into(), profile);
+    }
+
+    /// Makes the scheme called `name` the active one. Returns `false` (and changes nothing) if
+    /// there's no scheme with this name.
+    pub fn select(&mut self, name: &str) -> bool {
+        if !self.schemes.contains_key(name) {
+            return false;
         }
+        self.active = Some(name.to_string());
+        true
+    }
+
+    /// The active scheme's settings, if there is one.
+    pub fn active_profile(&self) -> Option<&RtsCameraControlProfile> {
+        self.active.as_ref().and_then(|name| self.schemes.get(name))
+    }
+}
+
+/// What the mouse wheel does while `RtsCameraControls` is rotating the camera.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Reflect)]
+pub enum ZoomWhileRotating {
+    /// Zoom as usual, using `zoom_anchor`.
+    #[default]
+    Normal,
+    /// Ignore the mouse wheel.
+    Disabled,
+    /// Zoom in and out around the focus (the point the camera rotates around), ignoring
+    /// `zoom_anchor`, so the rotation pivot stays put.
+    PivotDistance,
+}
+
+/// What `RtsCameraControls` zooms towards when the cursor misses the ground while using
+/// `ZoomAnchor::Cursor`.
+#[derive(Copy, Clone, Debug, PartialEq, Default, Reflect)]
+pub enum ZoomCursorMiss {
+    /// Zoom towards the focus, as if `zoom_anchor` was `ZoomAnchor::Focus`.
+    #[default]
+    FocusCentered,
+    /// Zoom towards a point on the ground in the direction of the cursor, `max_distance` away
+    /// from the camera (horizontally), as if the ground ended at the horizon there.
+    ClampToHorizon {
+        /// How far away the point on the horizon is.
+        max_distance: f32,
+    },
+}
+
+/// Add this to a camera with `RtsCameraControls` to zoom towards (and away from) the given entity,
+/// e.g. the player's base, instead of `zoom_anchor`. Zooming in converges on the entity. If
+/// `zoom_anchor` is `ZoomAnchor::Cursor`, the cursor takes precedence. If the entity is
+/// despawned, zooming falls back to `zoom_anchor`.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RtsCameraZoomTarget(pub Entity);
+
+/// Add this to a camera with `RtsCameraControls` to see what the controls are doing with it this
+/// frame, e.g. for a tutorial that waits for the player to rotate the camera. Updated
+/// automatically by the controls, in `RtsCameraControlsSystemSet`.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct RtsCameraInputState {
+    /// Whether the camera is being panned with the keyboard, edge panning, `RtsCameraPanIntent`
+    /// or `RtsCamera::set_pan_input`.
+    pub panning: bool,
+    /// Whether the camera is being rotated with the mouse or keyboard.
+    pub rotating: bool,
+    /// Whether the camera is being zoomed with the mouse wheel.
+    pub zooming: bool,
+    /// Whether the camera is being drag panned.
+    pub dragging: bool,
+}
+
+/// Add this to a camera with `RtsCameraControls` to pan it for one frame, e.g. to scroll towards
+/// an alert. The value is a world space direction, and is combined with the keyboard and edge pan
+/// for this frame (so it is limited to `pan_speed`), then reset to `Vec3::ZERO`.
+#[derive(Component, Copy, Clone, Debug, Default, PartialEq)]
+pub struct RtsCameraPanIntent(pub Vec3);
+
+/// Camera movement requested by an input source for a single frame. See
+/// `RtsCameraControls::apply_intent`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RtsCameraIntent {
+    /// The direction to pan in, relative to the camera's heading, where +X is right and +Y is
+    /// forward. A length of `1.0` pans at `pan_speed` (longer vectors are clamped), and shorter
+    /// vectors pan slower.
+    pub pan: Vec2,
+    /// How much to zoom, in mouse wheel lines. Positive values zoom in.
+    pub zoom: f32,
+    /// How much to rotate in radians, before the rotation zoom scale is applied. Positive values
+    /// rotate counterclockwise (looking from above).
+    pub rotate: f32,
+}
+
+/// What the `pan_speed` of `RtsCameraControls` is measured in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Reflect)]
+pub enum PanSpeedUnit {
+    /// World units per second, adjusted for zoom by `pan_speed_mode`.
+    #[default]
+    WorldUnits,
+    /// The visible width of the ground per second, so e.g. a `pan_speed` of `1.0` crosses one
+    /// screen per second at any zoom level and on maps of any scale.
+    ScreensPerSecond,
+}
+
+/// How the pan speed of `RtsCameraControls` changes as the camera zooms.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Reflect)]
+pub enum PanSpeedMode {
+    /// Pan at `pan_speed` when fully zoomed out, slowing down to half that when fully zoomed in
+    /// (unless `pan_speed_scales_with_zoom` is disabled).
+    #[default]
+    Fixed,
+    /// Pan at `pan_speed` when at `height_max`, and proportionally slower the lower the camera
+    /// is, so panning covers the same fraction of the visible area per second at any zoom.
+    ProportionalToHeight,
+}
+
+/// What the edge pan widths of `RtsCameraControls` are measured in. Either way, the edge pan zone
+/// is the same size on screen regardless of the monitor's scale factor, so it doesn't change when
+/// the window is moved between monitors with different DPI. The zones are measured from the edges
+/// of the camera's viewport (or the window, for cameras without one), so split-screen views each
+/// get their own.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Reflect)]
+pub enum EdgePanWidthUnit {
+    /// A fraction of the viewport's width (for `edge_pan_width_x`) or height (for
+    /// `edge_pan_width_y`), between `0.0` and `0.5`.
+    #[default]
+    Fraction,
+    /// Logical pixels, which are physical pixels divided by the window's scale factor.
+    Pixels,
+}
+
+/// Which point stays in place on screen while zooming with `RtsCameraControls`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Reflect)]
+pub enum ZoomAnchor {
+    /// Zoom towards the camera's focus.
+    #[default]
+    Focus,
+    /// Zoom towards the ground at the center of the screen. This is usually the same as the
+    /// focus, unless the ground between the camera and the focus is blocking the view.
+    ScreenCenter,
+    /// Zoom towards the ground under the mouse cursor.
+    Cursor,
+}
+
+impl RtsCameraControls {
+    /// The current speed and sensitivity settings.
+    pub fn profile(&self) -> RtsCameraControlProfile {
+        RtsCameraControlProfile {
+            key_rotate_speed: self.key_rotate_speed,
+            key_pitch_speed: self.key_pitch_speed,
+            rotation_zoom_scale_near: self.rotation_zoom_scale_near,
+            rotation_zoom_scale_far: self.rotation_zoom_scale_far,
+            rotate_min_zoom: self.rotate_min_zoom,
+            rotate_input_smoothing: self.rotate_input_smoothing,
+            drag_threshold: self.drag_threshold,
+            edge_pan_width_x: self.edge_pan_width_x,
+            edge_pan_width_y: self.edge_pan_width_y,
+            edge_pan_width_unit: self.edge_pan_width_unit,
+            edge_pan_width_near: self.edge_pan_width_near,
+            edge_pan_width_far: self.edge_pan_width_far,
+            edge_pan_activation_delay: self.edge_pan_activation_delay,
+            edge_pan_ramp_time: self.edge_pan_ramp_time,
+            edge_pan_hysteresis: self.edge_pan_hysteresis,
+            cursor_lean: self.cursor_lean,
+            pan_speed: self.pan_speed,
+            pan_zoom_boost: self.pan_zoom_boost,
+            orbit_speed: self.orbit_speed,
+            zoom_sensitivity: self.zoom_sensitivity,
+            zoom_fine_factor: self.zoom_fine_factor,
+            zoom_near_resistance: self.zoom_near_resistance,
+            zoom_input_smoothing: self.zoom_input_smoothing,
+            zoom_cursor_strength: self.zoom_cursor_strength,
+        }
+    }
+
+    /// Replaces all the speed and sensitivity settings with those from `profile`.
+    pub fn apply_profile(&mut self, profile: &RtsCameraControlProfile) {
+        self.key_rotate_speed = profile.key_rotate_speed;
+        self.key_pitch_speed = profile.key_pitch_speed;
+        self.rotation_zoom_scale_near = profile.rotation_zoom_scale_near;
+        self.rotation_zoom_scale_far = profile.rotation_zoom_scale_far;
+        self.rotate_min_zoom = profile.rotate_min_zoom;
+        self.rotate_input_smoothing = profile.rotate_input_smoothing;
+        self.drag_threshold = profile.drag_threshold;
+        self.edge_pan_width_x = profile.edge_pan_width_x;
+        self.edge_pan_width_y = profile.edge_pan_width_y;
+        self.edge_pan_width_unit = profile.edge_pan_width_unit;
+        self.edge_pan_width_near = profile.edge_pan_width_near;
+        self.edge_pan_width_far = profile.edge_pan_width_far;
+        self.edge_pan_activation_delay = profile.edge_pan_activation_delay;
+        self.edge_pan_ramp_time = profile.edge_pan_ramp_time;
+        self.edge_pan_hysteresis = profile.edge_pan_hysteresis;
+        self.cursor_lean = profile.cursor_lean;
+        self.pan_speed = profile.pan_speed;
+        self.pan_zoom_boost = profile.pan_zoom_boost;
+        self.orbit_speed = profile.orbit_speed;
+        self.zoom_sensitivity = profile.zoom_sensitivity;
+        self.zoom_fine_factor = profile.zoom_fine_factor;
+        self.zoom_near_resistance = profile.zoom_near_resistance;
+        self.zoom_input_smoothing = profile.zoom_input_smoothing;
+        self.zoom_cursor_strength = profile.zoom_cursor_strength;
+    }
+
+    /// Temporarily replaces the speed and sensitivity settings with those from `profile`, e.g.
+    /// while in a building placement mode. Call `pop_override` to go back to the previous
+    /// settings. Overrides can be layered, with each `pop_override` undoing the most recent one.
+    /// # Example
+    /// ```
+    /// # use bevy_rts_camera::{RtsCameraControlProfile, RtsCameraControls};
+    /// let mut controls = RtsCameraControls::default();
+    /// controls.push_override(RtsCameraControlProfile {
+    ///     pan_speed: 5.0,
+    ///     ..Default::default()
+    /// });
+    /// assert_eq!(controls.pan_speed, 5.0);
+    /// controls.pop_override();
+    /// assert_eq!(controls.pan_speed, RtsCameraControls::default().pan_speed);
+    /// ```
+    pub fn push_override(&mut self, profile: RtsCameraControlProfile) {
+        self.override_stack.push(self.profile());
+        self.apply_profile(&profile);
+    }
+
+    /// Restores the settings from before the most recent `push_override`. Does nothing if there
+    /// are no overrides.
+    pub fn pop_override(&mut self) {
+        if let Some(profile) = self.override_stack.pop() {
+            self.apply_profile(&profile);
+        }
+    }
+
+    /// Moves `cam` according to `intent`, using these controls' speed and sensitivity settings.
+    /// Like the built-in controls, it rotates first, then pans in the new direction, then zooms.
+    /// Use this to drive the camera from your own input source (e.g. a gamepad), while keeping
+    /// the same movement as the built-in controls. Set `enabled` to `false` if you want to
+    /// bypass the built-in input entirely.
+    /// # Example
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_rts_camera::{RtsCamera, RtsCameraControls, RtsCameraIntent};
+    /// fn gamepad_controls(
+    ///     mut cam_q: Query<(&mut RtsCamera, &RtsCameraControls)>,
+    ///     gamepads: Res<Gamepads>,
+    ///     axes: Res<Axis<GamepadAxis>>,
+    ///     time: Res<Time>,
+    /// ) {
+    ///     let Some(gamepad) = gamepads.iter().next() else {
+    ///         return;
+    ///     };
+    ///     let axis = |axis_type| axes.get(GamepadAxis::new(gamepad, axis_type)).unwrap_or(0.0);
+    ///     let intent = RtsCameraIntent {
+    ///         pan: Vec2::new(
+    ///             axis(GamepadAxisType::LeftStickX),
+    ///             axis(GamepadAxisType::LeftStickY),
+    ///         ),
+    ///         zoom: axis(GamepadAxisType::RightStickY) * time.delta_seconds() * 10.0,
+    ///         rotate: -axis(GamepadAxisType::RightStickX) * time.delta_seconds() * 2.0,
+    ///     };
+    ///     for (mut cam, controls) in cam_q.iter_mut() {
+    ///         controls.apply_intent(&mut cam, &intent, time.delta_seconds());
+    ///     }
+    /// }
+    /// ```
+    pub fn apply_intent(&self, cam: &mut RtsCamera, intent: &RtsCameraIntent, delta_seconds: f32) {
+        // The same order as the built-in controls (see `RtsCameraControlsSystemSet`)
+        self.apply_rotate(cam, intent.rotate);
+        self.apply_pan(cam, intent.pan, delta_seconds);
+        self.apply_zoom(cam, intent.zoom, None);
+    }
+
+    /// Pans `cam` relative to its heading, where +Y is forward, at up to `pan_speed` over
+    /// `delta_seconds`. This is what keyboard and edge panning use, so it can be used to test or
+    /// reuse the built-in movement without any input resources.
+    /// `PanSpeedUnit::ScreensPerSecond` assumes the default perspective projection with a square
+    /// viewport, as the camera's actual projection isn't known here.
+    pub fn apply_pan(&self, cam: &mut RtsCamera, pan: Vec2, delta_seconds: f32) {
+        self.pan_camera(cam, pan, delta_seconds, None);
+    }
+
+    /// Like `apply_pan`, but uses `projection` to work out the visible width of the ground.
+    fn pan_camera(
+        &self,
+        cam: &mut RtsCamera,
+        pan: Vec2,
+        delta_seconds: f32,
+        projection: Option<&Projection>,
+    ) {
+        // However many pan sources are combined, never go faster than `pan_speed`. Clamp rather
+        // than normalize, so that slower edge panning is preserved.
+        let pan = pan.clamp_length_max(1.0);
+        let delta = cam.pan_direction_for(Vec3::new(pan.x, 0.0, -pan.y));
+        let zoom_scale = match (self.pan_speed_unit, self.pan_speed_mode) {
+            (PanSpeedUnit::ScreensPerSecond, _) => visible_ground_width(cam, projection),
+            (_, PanSpeedMode::Fixed) if !self.pan_speed_scales_with_zoom => 1.0,
+            (_, PanSpeedMode::Fixed) => cam.target_zoom.remap(0.0, 1.0, 1.0, 0.5),
+            (_, PanSpeedMode::ProportionalToHeight) => {
+                cam.height_at_zoom(cam.target_zoom) / cam.height_max
+            }
+        };
+        let boost = 1.0 + self.pan_zoom_boost * (1.0 - cam.target_zoom.clamp(0.0, 1.0));
+        let world_delta = delta
+            * delta_seconds
+            * self.pan_speed
+            // Scale based on zoom so it (roughly) feels the same speed at different zoom levels
+            * zoom_scale
+            * boost;
+        // Non-finite input or settings (e.g. a `height_max` of `0.0`) would otherwise leave the
+        // camera stuck at NaN forever
+        if world_delta.is_finite() {
+            cam.pan_by(world_delta);
+        }
+    }
+
+    /// Zooms `cam` by `amount` mouse wheel lines, scaled by `zoom_sensitivity`. If `anchor` is
+    /// set, the focus also moves so that this world position stays in (roughly) the same place on
+    /// screen. This is what mouse wheel zooming uses, with the anchor from `zoom_anchor`.
+    pub fn apply_zoom(&self, cam: &mut RtsCamera, amount: f32, anchor: Option<Vec3>) {
+        let old_height = cam.height_at_zoom(cam.target_zoom);
+        let mut delta = amount * 0.5 * self.zoom_sensitivity;
+        let range = (cam.zoom_max - cam.zoom_min) * ZOOM_NEAR_RESISTANCE_RANGE;
+        // Without an upper limit (e.g. `zoom_max` is infinite) there's nothing to resist
+        if delta > 0.0 && self.zoom_near_resistance > 0.0 && range.is_finite() && range > 0.0 {
+            // How far into the last stretch before the limit the camera is, from 0.0 to 1.0
+            let depth = ((cam.target_zoom - (cam.zoom_max - range)) / range).clamp(0.0, 1.0);
+            delta *= 1.0 - self.zoom_near_resistance * depth;
+        }
+        cam.zoom_by(delta);
+        if let Some(anchor) = anchor {
+            // Scale the focus' distance from the anchor by the change in height, so the anchor
+            // stays in (roughly) the same place on screen. This is done in world space, so it's
+            // correct at any yaw. Since the camera's position relative to the focus scales by the
+            // same amount, it is exact as long as the angle doesn't change (i.e. `dynamic_angle`
+            // is disabled) and the anchor is level with the focus.
+            // At a height of zero (e.g. `height_min` is `0.0`) there's no ratio to scale by
+            if old_height <= f32::EPSILON {
+                return;
+            }
+            let new_height = cam.height_at_zoom(cam.target_zoom);
+            let new_focus =
+                anchor + (cam.target_focus.translation - anchor) * new_height / old_height;
+            if new_focus.is_finite() {
+                cam.set_target_focus_xz(new_focus.xz());
+            }
+        }
+    }
+
+    /// Rotates `cam` by `radians`, scaled by the rotation zoom scale. Does nothing when zoomed out
+    /// further than `rotate_min_zoom`. This is what mouse and keyboard rotation use.
+    pub fn apply_rotate(&self, cam: &mut RtsCamera, radians: f32) {
+        if cam.target_zoom < self.rotate_min_zoom {
+            return;
+        }
+        cam.rotate_by(radians * self.rotation_zoom_scale(cam));
+    }
+
+    /// The rotation sensitivity multiplier at the camera's current zoom.
+    fn rotation_zoom_scale(&self, cam: &RtsCamera) -> f32 {
+        cam.target_zoom.remap(
+            0.0,
+            1.0,
+            self.rotation_zoom_scale_far,
+            self.rotation_zoom_scale_near,
+        )
+    }
+
+    /// Whether `button_drag` is held.
+    fn dragging(&self, mouse_input: &ButtonInput<MouseButton>) -> bool {
+        self.button_drag.is_some_and(|btn| mouse_input.pressed(btn))
+    }
+
+    /// Whether any rotate button or `key_rotate_modifier` is held.
+    fn rotate_held(
+        &self,
+        mouse_input: &ButtonInput<MouseButton>,
+        keys: &ButtonInput<KeyCode>,
+    ) -> bool {
+        self.rotate_buttons().any(|btn| mouse_input.pressed(btn))
+            || self
+                .key_rotate_modifier
+                .is_some_and(|key| keys.pressed(key))
+    }
+
+    /// All the mouse buttons that rotate the camera.
+    fn rotate_buttons(&self) -> impl Iterator<Item = MouseButton> {
+        std::iter::once(self.button_rotate).chain(self.button_rotate_alt)
+    }
+
+    /// Sets both `edge_pan_width_x` and `edge_pan_width_y`. Set to `0.0` to disable edge panning.
+    pub fn set_edge_pan_width(&mut self, width: f32) {
+        self.edge_pan_width_x = width;
+        self.edge_pan_width_y = width;
+    }
+
+    /// Checks for invalid settings, logging a warning and clamping them to a valid value.
+    /// This is called automatically when `RtsCameraControls` is added.
+    pub fn validate(&mut self) {
+        validate_range(
+            "RtsCameraControls.key_rotate_speed",
+            &mut self.key_rotate_speed,
+            0.0,
+            f32::MAX,
+        );
+        let max_edge_pan_width = match self.edge_pan_width_unit {
+            EdgePanWidthUnit::Fraction => 0.5,
+            EdgePanWidthUnit::Pixels => f32::MAX,
+        };
+        validate_range(
+            "RtsCameraControls.edge_pan_width_x",
+            &mut self.edge_pan_width_x,
+            0.0,
+            max_edge_pan_width,
+        );
+        validate_range(
+            "RtsCameraControls.edge_pan_width_y",
+            &mut self.edge_pan_width_y,
+            0.0,
+            max_edge_pan_width,
+        );
+        validate_range(
+            "RtsCameraControls.edge_pan_width_near",
+            &mut self.edge_pan_width_near,
+            0.0,
+            f32::MAX,
+        );
+        validate_range(
+            "RtsCameraControls.edge_pan_width_far",
+            &mut self.edge_pan_width_far,
+            0.0,
+            f32::MAX,
+        );
+        validate_range(
+            "RtsCameraControls.pan_speed",
+            &mut self.pan_speed,
+            0.0,
+            f32::MAX,
+        );
+        validate_range(
+            "RtsCameraControls.rotate_input_smoothing",
+            &mut self.rotate_input_smoothing,
+            0.0,
+            1.0,
+        );
+        validate_range(
+            "RtsCameraControls.zoom_input_smoothing",
+            &mut self.zoom_input_smoothing,
+            0.0,
+            f32::MAX,
+        );
+        validate_range(
+            "RtsCameraControls.ui_scale",
+            &mut self.ui_scale,
+            0.01,
+            f32::MAX,
+        );
+        validate_range(
+            "RtsCameraControls.drag_threshold",
+            &mut self.drag_threshold,
+            0.0,
+            f32::MAX,
+        );
+        validate_range(
+            "RtsCameraControls.edge_pan_ramp_time",
+            &mut self.edge_pan_ramp_time,
+            0.0,
+            f32::MAX,
+        );
+        validate_range(
+            "RtsCameraControls.pan_zoom_boost",
+            &mut self.pan_zoom_boost,
+            0.0,
+            f32::MAX,
+        );
+        validate_range(
+            "RtsCameraControls.cursor_lean",
+            &mut self.cursor_lean,
+            0.0,
+            0.5,
+        );
+        validate_range(
+            "RtsCameraControls.orbit_speed",
+            &mut self.orbit_speed,
+            0.0,
+            f32::MAX,
+        );
+        validate_range(
+            "RtsCameraControls.zoom_fine_factor",
+            &mut self.zoom_fine_factor,
+            0.0,
+            1.0,
+        );
+        validate_range(
+            "RtsCameraControls.zoom_cursor_strength",
+            &mut self.zoom_cursor_strength,
+            0.0,
+            1.0,
+        );
+        validate_range(
+            "RtsCameraControls.zoom_near_resistance",
+            &mut self.zoom_near_resistance,
+            0.0,
+            1.0,
+        );
+        // These can be negative to invert the controls
+        for (name, value) in [
+            ("zoom_sensitivity", &mut self.zoom_sensitivity),
+            (
+                "rotation_zoom_scale_near",
+                &mut self.rotation_zoom_scale_near,
+            ),
+            ("rotation_zoom_scale_far", &mut self.rotation_zoom_scale_far),
+        ] {
+            validate_range(
+                &format!("RtsCameraControls.{name}"),
+                value,
+                f32::MIN,
+                f32::MAX,
+            );
+        }
+    }
+}
+
+#[cfg(feature = "bevy_ui")]
+fn sync_ui_scale(ui_scale: Option<Res<UiScale>>, mut controls_q: Query<&mut RtsCameraControls>) {
+    let Some(ui_scale) = ui_scale else {
+        return;
+    };
+    for mut controls in controls_q.iter_mut() {
+        if controls.ui_scale != ui_scale.0 {
+            controls.ui_scale = ui_scale.0;
+            controls.validate();
+        }
+    }
+}
+
+fn apply_control_scheme(
+    schemes: Res<RtsCameraControlSchemes>,
+    mut controls_q: Query<&mut RtsCameraControls>,
+) {
+    let Some(profile) = schemes.active_profile() else {
+        return;
+    };
+    for mut controls in controls_q.iter_mut() {
+        if !schemes.is_changed() && !controls.is_added() {
+            continue;
+        }
+        // Replace the settings underneath any overrides, so they're kept until popped
+        match controls.override_stack.first_mut() {
+            Some(base) => *base = *profile,
+            None => controls.apply_profile(profile),
+        }
+    }
+}
+
+fn validate_controls(mut controls_q: Query<&mut RtsCameraControls, Added<RtsCameraControls>>) {
+    for mut controls in controls_q.iter_mut() {
+        controls.validate();
     }
 }
 
 pub fn zoom(
     mut mouse_wheel: EventReader<MouseWheel>,
-    mut cam_q: Query<(&mut RtsCamera, &RtsCameraControls)>,
+    focus_gained: Res<FocusGained>,
+    controls_q: Query<(Entity, &RtsCameraControls)>,
+    mut cam_q: Query<
+        (
+            &Transform,
+            &mut RtsCamera,
+            Option<&RtsCameraZoomTarget>,
+            InputState,
+        ),
+        ControllableCamera,
+    >,
+    target_q: Query<&GlobalTransform>,
+    mut zoom_limit: EventWriter<RtsCameraZoomLimit>,
+    mut raycast: Raycast,
+    cursor_ray: Res<CursorRay>,
+    ground_q: Query<Entity, With<Ground>>,
+    cursor_camera: Res<CursorCamera>,
+    time: Res<Time<Real>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    keys: Res<ButtonInput<KeyCode>>,
+    actions: Res<RtsCameraAllowedActions>,
+    // Recent wheel input that is still being spread out by `zoom_input_smoothing`, as the amount
+    // and how long ago it happened
+    mut pending_wheel: Local<EntityHashMap<VecDeque<(f32, f32)>>>,
+    mut zoomed: ResMut<ZoomedCameras>,
 ) {
-    for (mut cam, cam_controls) in cam_q.iter_mut().filter(|(_, ctrl)| ctrl.enabled) {
-        let zoom_amount = mouse_wheel
+    // Forget cameras that have been despawned
+    pending_wheel.retain(|entity, _| cam_q.contains(*entity));
+    zoomed.0.clear();
+    // Kept apart, as only pixels are affected by `ui_scale`
+    let (wheel_lines, wheel_pixels) =
+        mouse_wheel
             .read()
-            .map(|event| match event.unit {
-                MouseScrollUnit::Line => event.y,
-                MouseScrollUnit::Pixel => event.y * 0.001,
-            })
-            .fold(0.0, |acc, val| acc + val);
-        let new_zoom =
-            (cam.target_zoom + zoom_amount * 0.5 * cam_controls.zoom_sensitivity).clamp(0.0, 1.0);
-        cam.target_zoom = new_zoom;
+            .fold((0.0, 0.0), |(lines, pixels), event| match event.unit {
+                MouseScrollUnit::Line => (lines + event.y, pixels),
+                MouseScrollUnit::Pixel => (lines, pixels + event.y),
+            });
+    for (entity, cam_controls) in controls_q.iter().filter(|(_, ctrl)| ctrl.enabled) {
+        let cam_entity = cam_controls.camera.unwrap_or(entity);
+        let Ok((cam_tfm, mut cam, zoom_target, input_state)) = cam_q.get_mut(cam_entity) else {
+            continue;
+        };
+        let rotating = cam_controls.rotate_held(&mouse_input, &keys);
+        let ignored = !actions.allow_zoom
+            || (rotating && cam_controls.zoom_while_rotating == ZoomWhileRotating::Disabled)
+            || !cursor_camera.claims(cam_entity)
+            || (focus_gained.0 && cam_controls.ignore_input_on_focus)
+            || (cam_controls.drag_locks_zoom && cam_controls.dragging(&mouse_input));
+        let fine = cam_controls
+            .key_zoom_fine
+            .is_some_and(|key| keys.pressed(key));
+        let wheel_amount = wheel_lines + wheel_pixels * 0.001 / cam_controls.ui_scale;
+        let wheel_amount = if ignored {
+            0.0
+        } else if fine {
+            wheel_amount * cam_controls.zoom_fine_factor
+        } else {
+            wheel_amount
+        };
+        let window = match cam_controls.zoom_input_smoothing {
+            0.0 if cam_controls.zoom_event_spread => ZOOM_EVENT_SPREAD_TIME,
+            window => window,
+        };
+        let zoom_amount = if window > 0.0 {
+            // With `zoom_event_spread`, up to one notch is applied straight away
+            let instant = if cam_controls.zoom_event_spread {
+                wheel_amount.clamp(-1.0, 1.0)
+            } else {
+                0.0
+            };
+            let pending = pending_wheel.entry(cam_entity).or_default();
+            if wheel_amount != instant {
+                pending.push_back((wheel_amount - instant, 0.0));
+            }
+            // Each frame's input is applied evenly over the window, starting straight away
+            let delta = time.delta_seconds();
+            let mut amount = instant;
+            for (wheel, age) in pending.iter_mut() {
+                amount += *wheel * ((*age + delta).min(window) - age.min(window)) / window;
+                *age += delta;
+            }
+            while pending.front().is_some_and(|(_, age)| *age >= window) {
+                pending.pop_front();
+            }
+            amount
+        } else {
+            pending_wheel.remove(&cam_entity);
+            wheel_amount
+        };
+        if zoom_amount == 0.0 {
+            continue;
+        }
+
+        // Ignored if the target has been despawned
+        let zoom_target = zoom_target
+            .and_then(|target| target_q.get(target.0).ok())
+            .map(|tfm| tfm.translation());
+        let anchor = match cam_controls.zoom_anchor {
+            _ if rotating
+                && cam_controls.zoom_while_rotating == ZoomWhileRotating::PivotDistance =>
+            {
+                None
+            }
+            ZoomAnchor::Focus | ZoomAnchor::ScreenCenter if zoom_target.is_some() => zoom_target,
+            ZoomAnchor::Focus => None,
+            ZoomAnchor::ScreenCenter => ray_to_ground(
+                &mut raycast,
+                &ground_q,
+                Ray3d::new(cam_tfm.translation, cam_tfm.forward().into()),
+                &cam,
+            ),
+            ZoomAnchor::Cursor => cam.cursor_ground.map(|hit| hit.position).or_else(|| {
+                let ray = (**cursor_ray)?;
+                ray_to_ground(&mut raycast, &ground_q, ray, &cam).or_else(|| {
+                    match cam_controls.zoom_cursor_miss {
+                        ZoomCursorMiss::FocusCentered => None,
+                        ZoomCursorMiss::ClampToHorizon { max_distance } => {
+                            // The point on the ground towards the cursor, `max_distance` away
+                            let dir =
+                                Vec3::new(ray.direction.x, 0.0, ray.direction.z).try_normalize()?;
+                            let point = ray.origin + dir * max_distance;
+                            Some(Vec3::new(
+                                point.x,
+                                cam.ground_height_at(point.xz()),
+                                point.z,
+                            ))
+                        }
+                    }
+                })
+            }),
+        };
+        // Anchoring part of the way between the focus and the cursor moves the focus that
+        // proportion of the way it would have
+        let anchor = match cam_controls.zoom_anchor {
+            ZoomAnchor::Cursor => anchor.map(|point| {
+                cam.target_focus
+                    .translation
+                    .lerp(point, cam_controls.zoom_cursor_strength)
+            }),
+            _ => anchor,
+        };
+        cam_controls.apply_zoom(&mut cam, zoom_amount, anchor);
+        zoomed.0.insert(cam_entity);
+        if let Some(mut input_state) = input_state {
+            input_state.zooming = true;
+        }
+        send_zoom_limit(&cam, cam_entity, &mut zoom_limit);
     }
 }
 
+/// Sends `RtsCameraZoomLimit` if the controls have zoomed `cam` to one of its limits.
+fn send_zoom_limit(
+    cam: &RtsCamera,
+    cam_entity: Entity,
+    zoom_limit: &mut EventWriter<RtsCameraZoomLimit>,
+) {
+    let new_zoom = cam.target_zoom;
+    if new_zoom == cam.zoom_min || new_zoom == cam.zoom_max {
+        zoom_limit.send(RtsCameraZoomLimit {
+            entity: cam_entity,
+            at_min: new_zoom == cam.zoom_min,
+            at_max: new_zoom == cam.zoom_max,
+        });
+    }
+}
+
+/// How long in seconds bursts of wheel input are spread over with
+/// `RtsCameraControls::zoom_event_spread`, when `zoom_input_smoothing` isn't set.
+const ZOOM_EVENT_SPREAD_TIME: f32 = 0.1;
+
+/// The fraction of the zoom range before `RtsCamera::zoom_max` over which
+/// `RtsCameraControls::zoom_near_resistance` slows zooming in.
+const ZOOM_NEAR_RESISTANCE_RANGE: f32 = 0.2;
+
+/// The viewport width in logical pixels rotation is scaled by when there's no viewport or primary
+/// window to measure, so mouse motion isn't dropped.
+const ROTATE_FALLBACK_VIEW_WIDTH: f32 = 1280.0;
+
+/// How long in seconds edge panning stays off after zooming, when
+/// `RtsCameraControls::suppress_pan_while_zooming` is enabled.
+const ZOOM_EDGE_PAN_COOLDOWN: f32 = 0.3;
+
 pub fn pan(
-    mut cam_q: Query<(&mut RtsCamera, &RtsCameraControls)>,
+    controls_q: Query<(Entity, &RtsCameraControls)>,
+    mut cam_q: Query<PanCamera, ControllableCamera>,
     button_input: Res<ButtonInput<KeyCode>>,
     mouse_input: Res<ButtonInput<MouseButton>>,
-    primary_window_q: Query<&Window, With<PrimaryWindow>>,
+    window: ControlsWindow,
     time: Res<Time<Real>>,
+    mut last_edge_pan: Local<EntityHashMap<Vec2>>,
+    mut edge_pan_time: Local<EntityHashMap<f32>>,
+    cursor_camera: Res<CursorCamera>,
+    zoomed: Res<ZoomedCameras>,
+    // How long it's been since each camera was last zoomed by the controls
+    mut since_zoom: Local<EntityHashMap<f32>>,
+    actions: Res<RtsCameraAllowedActions>,
+    mut mouse_motion: EventReader<MouseMotion>,
+    // The cursor position used for edge panning while the real one is hidden, for
+    // `edge_pan_virtual_cursor`
+    mut virtual_cursor: Local<EntityHashMap<Vec2>>,
+    // How far each camera's edge panning has sped up, and in which direction, for
+    // `edge_pan_ramp_time`
+    mut edge_pan_speed: Local<EntityHashMap<(f32, Vec2)>>,
 ) {
-    for (mut cam, controller) in cam_q.iter_mut().filter(|(_, ctrl)| ctrl.enabled) {
-        if controller
-            .button_drag
-            .map_or(false, |btn| mouse_input.pressed(btn))
-        {
+    // Forget cameras that have been despawned
+    last_edge_pan.retain(|entity, _| cam_q.contains(*entity));
+    edge_pan_time.retain(|entity, _| cam_q.contains(*entity));
+    since_zoom.retain(|entity, _| cam_q.contains(*entity));
+    virtual_cursor.retain(|entity, _| cam_q.contains(*entity));
+    edge_pan_speed.retain(|entity, _| cam_q.contains(*entity));
+    let motion: Vec2 = mouse_motion.read().map(|event| event.delta).sum();
+    for (entity, controller) in controls_q.iter().filter(|(_, ctrl)| ctrl.enabled) {
+        let cam_entity = controller.camera.unwrap_or(entity);
+        let Ok((mut cam, camera, pan_intent, projection, input_state)) = cam_q.get_mut(cam_entity)
+        else {
+            continue;
+        };
+        let pan_intent = pan_intent.map_or(Vec3::ZERO, |mut intent| std::mem::take(&mut intent.0));
+        // Avoid triggering change detection every frame
+        let pan_input = if cam.pan_input != Vec2::ZERO {
+            std::mem::take(&mut cam.pan_input)
+        } else {
+            Vec2::ZERO
+        };
+        // When several pan sources are active they're resolved in this order: drag pan (handled in
+        // `grab_pan`) suppresses edge pan, and keyboard pan is added to drag and edge pan unless
+        // `drag_exclusive` is set.
+        let dragging = controller.dragging(&mouse_input);
+        if dragging && controller.drag_exclusive {
+            if cam.edge_pan.any() {
+                cam.edge_pan = RtsCameraEdgePan::default();
+            }
             continue;
         }
 
-        let mut delta = Vec3::ZERO;
+        let pan_sign = if controller.invert_pan { -1.0 } else { 1.0 };
 
-        // Keyboard pan
-        if button_input.pressed(controller.key_up) {
-            delta += Vec3::from(cam.target_focus.forward())
+        // Relative to the camera's heading, where +Y is forward
+        let flat_focus = cam.flat_target_focus();
+        let mut delta = Vec2::new(
+            pan_intent.dot(*flat_focus.right()),
+            pan_intent.dot(*flat_focus.forward()),
+        ) + pan_input;
+
+        // Either binding of each pan key, where +Y is up
+        let pressed = |key: KeyCode, alt: Option<KeyCode>| {
+            button_input.pressed(key) || alt.is_some_and(|alt| button_input.pressed(alt))
+        };
+        let mut key_delta = Vec2::ZERO;
+        if pressed(controller.key_up, controller.key_pan_up) {
+            key_delta.y += 1.0;
+        }
+        if pressed(controller.key_down, controller.key_pan_down) {
+            key_delta.y -= 1.0;
         }
-        if button_input.pressed(controller.key_down) {
-            delta += Vec3::from(cam.target_focus.back())
+        if pressed(controller.key_left, controller.key_pan_left) {
+            key_delta.x -= 1.0;
         }
-        if button_input.pressed(controller.key_left) {
-            delta += Vec3::from(cam.target_focus.left())
+        if pressed(controller.key_right, controller.key_pan_right) {
+            key_delta.x += 1.0;
         }
-        if button_input.pressed(controller.key_right) {
-            delta += Vec3::from(cam.target_focus.right())
+
+        if controller.orbit_mode {
+            // Keyboard orbit
+            if actions.allow_rotate {
+                let orbit = key_delta * controller.orbit_speed * time.delta_seconds();
+                cam.rotate_by(orbit.x);
+                // `max_angle` may have been set below zero since it was validated
+                cam.min_angle = (cam.min_angle + orbit.y).clamp(0.0, cam.max_angle.max(0.0));
+            }
+        } else {
+            // Keyboard pan
+            if controller.pan_fixed_compass {
+                // Convert from world directions to the camera's heading
+                let world = Vec3::new(key_delta.x, 0.0, -key_delta.y);
+                key_delta = Vec2::new(
+                    world.dot(*flat_focus.right()),
+                    world.dot(*flat_focus.forward()),
+                );
+            }
+            delta += key_delta * pan_sign;
         }
 
-        // Edge pan
-        if delta.length_squared() == 0.0 && !mouse_input.pressed(controller.button_rotate) {
-            if let Ok(primary_window) = primary_window_q.get_single() {
-                if let Some(cursor_position) = primary_window.cursor_position() {
-                    let win_w = primary_window.width();
-                    let win_h = primary_window.height();
-                    let pan_width = win_h * controller.edge_pan_width;
+        // Edge pan. This is added to the keyboard pan before normalizing, so e.g. holding up while
+        // the cursor is at the left edge pans diagonally at normal speed, and opposite directions
+        // cancel out.
+        let rotating = controller.rotate_held(&mouse_input, &button_input);
+        let since = since_zoom.entry(cam_entity).or_insert(f32::INFINITY);
+        if zoomed.0.contains(&cam_entity) {
+            *since = 0.0;
+        } else {
+            *since += time.delta_seconds();
+        }
+        let zoom_suppressed =
+            *since < ZOOM_EDGE_PAN_COOLDOWN && controller.suppress_pan_while_zooming;
+        let mut edges = RtsCameraEdgePan::default();
+        if !dragging
+            && (!rotating || controller.edge_pan_during_rotate)
+            && !zoom_suppressed
+            && cursor_camera.claims(cam_entity)
+        {
+            let edge_pan_sign = if controller.edge_pan_invert {
+                -pan_sign
+            } else {
+                pan_sign
+            };
+            // The cursor is tracked in logical pixels, so the zones don't change size when the
+            // window's scale factor does
+            if let Some(win_size) = window.size() {
+                let cursor_position = window.cursor_position();
+                let hidden = window.cursor_hidden();
+                let cursor_position = if controller.edge_pan_virtual_cursor {
+                    let virtual_cursor = virtual_cursor.entry(cam_entity).or_insert(win_size / 2.0);
+                    match cursor_position {
+                        Some(pos) if !hidden => *virtual_cursor = pos,
+                        _ if hidden && !rotating => {
+                            *virtual_cursor = (*virtual_cursor + motion).clamp(Vec2::ZERO, win_size)
+                        }
+                        _ => {}
+                    }
+                    if hidden {
+                        Some(*virtual_cursor)
+                    } else {
+                        cursor_position
+                    }
+                } else {
+                    cursor_position
+                };
+                // The zones are worked out in viewport-normalized space (0 to 1 across the
+                // viewport), so they're the same whatever the size of the camera's viewport
+                let view = camera
+                    .and_then(|camera| camera.logical_viewport_rect())
+                    .unwrap_or(Rect::from_corners(Vec2::ZERO, win_size));
+                let view_size = view.size().max(Vec2::ONE);
+                // Outside of the viewport counts as leaving it, like leaving the window
+                let cursor = cursor_position
+                    .map(|pos| (pos - view.min) / view_size)
+                    .filter(|cursor| {
+                        cursor.cmpge(Vec2::ZERO).all() && cursor.cmple(Vec2::ONE).all()
+                    });
+                if let Some(cursor) = cursor {
+                    let mut edge_delta = Vec2::ZERO;
+                    let zoom_scale = controller.edge_pan_width_far.lerp(
+                        controller.edge_pan_width_near,
+                        cam.target_zoom.clamp(0.0, 1.0),
+                    );
+                    let width_x = controller.edge_pan_width_x * zoom_scale;
+                    let width_y = controller.edge_pan_width_y * zoom_scale;
+                    let pan_width = match controller.edge_pan_width_unit {
+                        EdgePanWidthUnit::Fraction => Vec2::new(width_x, width_y),
+                        EdgePanWidthUnit::Pixels => {
+                            Vec2::new(width_x, width_y) * controller.ui_scale / view_size
+                        }
+                    }
+                    .min(Vec2::splat(0.5));
+                    let (pan_width_x, pan_width_y) = (pan_width.x, pan_width.y);
+                    // Once panning towards an edge, its zone grows by `edge_pan_hysteresis`, so
+                    // the cursor jittering right at the boundary doesn't flicker panning on and off
+                    let last = last_edge_pan.get(&cam_entity).copied().unwrap_or_default();
+                    let hysteresis =
+                        controller.edge_pan_hysteresis * controller.ui_scale / view_size;
+                    let margin = |panning: bool| {
+                        if panning {
+                            hysteresis
+                        } else {
+                            Vec2::ZERO
+                        }
+                    };
+                    // How fast to pan based on how far into the edge zone the cursor is
+                    let speed = |depth: f32, pan_width: f32| {
+                        if controller.edge_pan_ramp {
+                            (depth / pan_width).clamp(0.0, 1.0)
+                        } else {
+                            1.0
+                        }
+                    };
                     // Pan left
-                    if cursor_position.x < pan_width {
-                        delta += Vec3::from(cam.target_focus.left())
+                    if cursor.x < pan_width_x + margin(last.x < 0.0).x {
+                        edge_delta.x -= speed(pan_width_x - cursor.x, pan_width_x)
                     }
                     // Pan right
-                    if cursor_position.x > win_w - pan_width {
-                        delta += Vec3::from(cam.target_focus.right())
+                    if cursor.x > 1.0 - pan_width_x - margin(last.x > 0.0).x {
+                        edge_delta.x += speed(cursor.x - (1.0 - pan_width_x), pan_width_x)
                     }
                     // Pan up
-                    if cursor_position.y < pan_width {
-                        delta += Vec3::from(cam.target_focus.forward())
+                    if cursor.y < pan_width_y + margin(last.y > 0.0).y {
+                        edge_delta.y += speed(pan_width_y - cursor.y, pan_width_y)
                     }
                     // Pan down
-                    if cursor_position.y > win_h - pan_width {
-                        delta += Vec3::from(cam.target_focus.back())
+                    if cursor.y > 1.0 - pan_width_y - margin(last.y < 0.0).y {
+                        edge_delta.y -= speed(cursor.y - (1.0 - pan_width_y), pan_width_y)
+                    }
+                    // How long the cursor has been in the edge pan zone
+                    let time_in_zone = edge_pan_time.entry(cam_entity).or_default();
+                    if edge_delta == Vec2::ZERO {
+                        *time_in_zone = 0.0;
+                    } else {
+                        *time_in_zone += time.delta_seconds();
                     }
+                    if *time_in_zone < controller.edge_pan_activation_delay {
+                        edge_delta = Vec2::ZERO;
+                    }
+                    last_edge_pan.insert(cam_entity, edge_delta);
+                    edges = RtsCameraEdgePan {
+                        left: edge_delta.x < 0.0,
+                        right: edge_delta.x > 0.0,
+                        up: edge_delta.y > 0.0,
+                        down: edge_delta.y < 0.0,
+                    };
+                    if controller.edge_pan_ramp_time > 0.0 {
+                        let (speed, direction) = edge_pan_speed.entry(cam_entity).or_default();
+                        let step = time.delta_seconds() / controller.edge_pan_ramp_time;
+                        if edge_delta == Vec2::ZERO {
+                            // Keep going the same way while slowing down
+                            *speed = (*speed - step).max(0.0);
+                        } else {
+                            *speed = (*speed + step).min(1.0);
+                            *direction = edge_delta;
+                        }
+                        edge_delta = *direction * *speed;
+                    }
+                    delta += edge_delta * edge_pan_sign;
+                } else if controller.edge_pan_continue_outside {
+                    delta +=
+                        last_edge_pan.get(&cam_entity).copied().unwrap_or_default() * edge_pan_sign;
                 }
             }
         }
+        // Avoid triggering change detection every frame
+        if cam.edge_pan != edges {
+            cam.edge_pan = edges;
+        }
 
-        let new_target = cam.target_focus.translation
-            + delta.normalize_or_zero()
-            * time.delta_seconds()
-            * controller.pan_speed
-            // Scale based on zoom so it (roughly) feels the same speed at different zoom levels
-            * cam.target_zoom.remap(0.0, 1.0, 1.0, 0.5);
-        cam.target_focus.translation = new_target;
+        // Avoid triggering change detection every frame
+        if actions.allow_pan && delta != Vec2::ZERO {
+            controller.pan_camera(&mut cam, delta, time.delta_seconds(), projection);
+            if let Some(mut input_state) = input_state {
+                input_state.panning = true;
+            }
+        }
+    }
+}
+
+fn cursor_lean(
+    controls_q: Query<(Entity, &RtsCameraControls)>,
+    mut cam_q: Query<&mut RtsCamera, ControllableCamera>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    cursor_camera: Res<CursorCamera>,
+    time: Res<Time<Real>>,
+) {
+    for (entity, controller) in controls_q.iter() {
+        let cam_entity = controller.camera.unwrap_or(entity);
+        let Ok(mut cam) = cam_q.get_mut(cam_entity) else {
+            continue;
+        };
+        let target = match cam.cursor_ground {
+            Some(hit)
+                if controller.enabled
+                    && controller.cursor_lean > 0.0
+                    && !controller.dragging(&mouse_input)
+                    && cursor_camera.claims(cam_entity) =>
+            {
+                // The cursor ray comes from the leaned view, so take the lean back off
+                (hit.position.xz() - cam.target_focus_xz() - cam.lean) * controller.cursor_lean
+            }
+            _ => Vec2::ZERO,
+        };
+        let target = if target.is_finite() {
+            target
+        } else {
+            Vec2::ZERO
+        };
+        // Smoothed the same way as the focus
+        let t = (1.0 - cam.focus_smoothing.powi(7).powf(time.delta_seconds())).clamp(0.0, 1.0);
+        let lean = if cam.lean.distance(target) < cam.smoothing_snap_threshold {
+            target
+        } else {
+            cam.lean.lerp(target, t)
+        };
+        // Avoid triggering change detection every frame
+        if cam.lean != lean {
+            cam.lean = lean;
+        }
+    }
+}
+
+fn reset_input_state(mut state_q: Query<&mut RtsCameraInputState>) {
+    for mut state in state_q.iter_mut() {
+        // Avoid triggering change detection every frame
+        if *state != RtsCameraInputState::default() {
+            *state = RtsCameraInputState::default();
+        }
     }
 }
 
 pub fn grab_pan(
-    mut cam_q: Query<(
-        &Transform,
-        &mut RtsCamera,
-        &RtsCameraControls,
-        &Camera,
-        &Projection,
-    )>,
+    controls_q: Query<(Entity, &RtsCameraControls)>,
+    mut cam_q: Query<
+        (&Transform, &mut RtsCamera, &Camera, &Projection, InputState),
+        ControllableCamera,
+    >,
     mut mouse_motion: EventReader<MouseMotion>,
     mouse_button: Res<ButtonInput<MouseButton>>,
     mut raycast: Raycast,
     cursor_ray: Res<CursorRay>,
-    mut ray_hit: Local<Option<Vec3>>,
+    // Where on the ground each camera's drag started, for `lock_on_drag`
+    mut ray_hit: Local<EntityHashMap<Vec3>>,
     ground_q: Query<Entity, With<Ground>>,
-    mut primary_window_q: Query<&mut Window, With<PrimaryWindow>>,
-    mut previous_mouse_grab_mode: Local<CursorGrabMode>,
+    mut window: ControlsWindow,
+    focus_gained: Res<FocusGained>,
+    cursor_camera: Res<CursorCamera>,
+    actions: Res<RtsCameraAllowedActions>,
+    // Cameras that locked the cursor when drag panning started
+    mut locked: Local<EntityHashSet>,
+    // How far the mouse has moved since `button_drag` was pressed, for `drag_threshold`
+    mut drag_moved: Local<EntityHashMap<f32>>,
 ) {
-    for (cam_tfm, mut cam, controller, camera, projection) in
-        cam_q.iter_mut().filter(|(_, _, ctrl, _, _)| ctrl.enabled)
-    {
+    // Forget cameras that have been despawned
+    drag_moved.retain(|entity, _| cam_q.contains(*entity));
+    ray_hit.retain(|entity, _| cam_q.contains(*entity));
+    let mouse_delta = mouse_motion.read().map(|e| e.delta).sum::<Vec2>();
+    // Cameras driven by enabled controls this frame
+    let mut driven = EntityHashSet::default();
+    for (entity, controller) in controls_q.iter().filter(|(_, ctrl)| ctrl.enabled) {
+        let cam_entity = controller.camera.unwrap_or(entity);
+        let Ok((cam_tfm, mut cam, camera, projection, input_state)) = cam_q.get_mut(cam_entity)
+        else {
+            continue;
+        };
+        driven.insert(cam_entity);
+        let claimed = cursor_camera.claims(cam_entity);
         let Some(drag_button) = controller.button_drag else {
             continue;
         };
 
-        if mouse_button.just_pressed(drag_button) && controller.lock_on_drag {
-            if let Ok(mut primary_window) = primary_window_q.get_single_mut() {
-                *previous_mouse_grab_mode = primary_window.cursor.grab_mode;
-                primary_window.cursor.grab_mode = CursorGrabMode::Locked;
-                primary_window.cursor.visible = false;
+        let just_pressed = mouse_button.just_pressed(drag_button);
+        if just_pressed {
+            drag_moved.insert(cam_entity, 0.0);
+            if controller.lock_on_drag && claimed {
+                let hit = (**cursor_ray).and_then(|cursor_ray| {
+                    ray_to_ground(&mut raycast, &ground_q, cursor_ray, &cam)
+                });
+                match hit {
+                    Some(hit) => ray_hit.insert(cam_entity, hit),
+                    None => ray_hit.remove(&cam_entity),
+                };
             }
+        }
 
-            if let Some(cursor_ray) = **cursor_ray {
-                *ray_hit = raycast
-                    .cast_ray(
-                        cursor_ray,
-                        &RaycastSettings {
-                            filter: &|entity| ground_q.get(entity).is_ok(),
-                            ..default()
-                        },
-                    )
-                    .first()
-                    .map(|(_, hit)| hit.position());
-            }
+        // Controls added while the button is already held start dragging straight away
+        let threshold = controller.drag_threshold * controller.ui_scale;
+        let moved = drag_moved.entry(cam_entity).or_insert(f32::INFINITY);
+        let was_dragging = *moved >= threshold && !just_pressed;
+        if mouse_button.pressed(drag_button) {
+            *moved += mouse_delta.length();
+        }
+        let drag_started = *moved >= threshold;
+
+        if drag_started
+            && !was_dragging
+            && controller.lock_on_drag
+            && claimed
+            && window.lock_cursor()
+        {
+            locked.insert(cam_entity);
         }
 
         if mouse_button.just_released(drag_button) {
-            *ray_hit = None;
+            ray_hit.remove(&cam_entity);
+            drag_moved.remove(&cam_entity);
 
-            if let Ok(mut primary_window) = primary_window_q.get_single_mut() {
-                primary_window.cursor.grab_mode = *previous_mouse_grab_mode;
-                primary_window.cursor.visible = true;
+            if locked.remove(&cam_entity) {
+                window.unlock_cursor();
             }
         }
 
-        if mouse_button.pressed(drag_button) {
-            let mut mouse_delta = mouse_motion.read().map(|e| e.delta).sum::<Vec2>();
-
-            let mut multiplier = 1.0;
-            let vp_size = camera.logical_viewport_size().unwrap();
-            match *projection {
-                Projection::Perspective(ref p) => {
-                    mouse_delta *= Vec2::new(p.fov * p.aspect_ratio, p.fov) / vp_size;
-                    multiplier = (*ray_hit).map_or_else(
-                        || cam_tfm.translation.distance(cam.focus.translation),
-                        |hit| hit.distance(cam_tfm.translation),
-                    );
-                }
-                Projection::Orthographic(ref p) => {
-                    mouse_delta *= Vec2::new(p.area.width(), p.area.height()) / vp_size;
-                }
+        if mouse_button.pressed(drag_button) && drag_started && claimed && actions.allow_pan {
+            if focus_gained.0 && controller.ignore_input_on_focus {
+                continue;
+            }
+            if let Some(mut input_state) = input_state {
+                input_state.dragging = true;
+            }
+            let Some(vp_size) = window.view_size(camera) else {
+                continue;
+            };
+            let distance = ray_hit.get(&cam_entity).map_or_else(
+                || cam_tfm.translation.distance(cam.focus.translation),
+                |hit| hit.distance(cam_tfm.translation),
+            );
+            let delta = drag_to_world(&cam, mouse_delta, vp_size, projection, distance);
+            cam.pan_by(delta);
+            // Skip smoothing so the ground sticks to the cursor
+            if !controller.smooth_drag {
+                cam.snap = true;
             }
+        }
+    }
+
+    // Controls that were removed or disabled while dragging can't unlock the cursor themselves
+    let was_locked = !locked.is_empty();
+    locked.retain(|cam_entity| driven.contains(cam_entity));
+    if was_locked && locked.is_empty() {
+        window.unlock_cursor();
+    }
+}
 
-            let mut delta = Vec3::ZERO;
-            delta += cam.target_focus.forward() * mouse_delta.y;
-            delta += cam.target_focus.right() * -mouse_delta.x;
-            cam.target_focus.translation += delta * multiplier;
+pub fn touch(
+    controls_q: Query<(Entity, &RtsCameraControls)>,
+    mut cam_q: Query<(&Transform, &mut RtsCamera, &Camera, &Projection), ControllableCamera>,
+    touches: Res<Touches>,
+    actions: Res<RtsCameraAllowedActions>,
+    mut raycast: Raycast,
+    ground_q: Query<Entity, With<Ground>>,
+    mut zoom_limit: EventWriter<RtsCameraZoomLimit>,
+) {
+    // Gestures are only recognised with exactly two fingers
+    let mut active = touches.iter();
+    let (Some(a), Some(b), None) = (active.next(), active.next(), active.next()) else {
+        return;
+    };
+    let (prev_a, prev_b) = (a.previous_position(), b.previous_position());
+    let (cur_a, cur_b) = (a.position(), b.position());
+    let centroid = (cur_a + cur_b) / 2.0;
+    let centroid_delta = centroid - (prev_a + prev_b) / 2.0;
+    let pinch = cur_a.distance(cur_b) - prev_a.distance(prev_b);
+    let twist = (prev_b - prev_a).angle_between(cur_b - cur_a);
+
+    let controls = || {
+        controls_q
+            .iter()
+            .filter(|(_, ctrl)| ctrl.enabled && ctrl.touch)
+            .map(|(entity, ctrl)| (ctrl.camera.unwrap_or(entity), ctrl))
+    };
+    // Only the camera under the gesture is affected, chosen the same way as `CursorCamera`
+    let touched = topmost_camera_at(
+        centroid,
+        controls().filter_map(|(cam_entity, _)| {
+            let (_, _, camera, _) = cam_q.get(cam_entity).ok()?;
+            camera
+                .is_active
+                .then(|| (cam_entity, camera.order, camera.logical_viewport_rect()))
+        }),
+    );
+    for (cam_entity, controller) in
+        controls().filter(|(cam_entity, _)| Some(*cam_entity) == touched)
+    {
+        let Ok((cam_tfm, mut cam, camera, projection)) = cam_q.get_mut(cam_entity) else {
+            continue;
+        };
+        let Some(vp_size) = camera.logical_viewport_size() else {
+            continue;
+        };
+        if actions.allow_pan {
+            let distance = cam_tfm.translation.distance(cam.focus.translation);
+            let delta = drag_to_world(&cam, centroid_delta, vp_size, projection, distance);
+            cam.pan_by(delta);
+        }
+        if actions.allow_zoom && pinch != 0.0 {
+            // The pinch takes the place of the cursor for `ZoomAnchor::Cursor`
+            let anchor = match controller.zoom_anchor {
+                ZoomAnchor::Focus => None,
+                ZoomAnchor::ScreenCenter => ray_to_ground(
+                    &mut raycast,
+                    &ground_q,
+                    Ray3d::new(cam_tfm.translation, cam_tfm.forward().into()),
+                    &cam,
+                ),
+                ZoomAnchor::Cursor => {
+                    // Offset by the viewport's position, for cameras that don't cover the window
+                    let viewport_min = camera
+                        .logical_viewport_rect()
+                        .map_or(Vec2::ZERO, |rect| rect.min);
+                    camera
+                        .viewport_to_world(
+                            &GlobalTransform::from(*cam_tfm),
+                            centroid - viewport_min,
+                        )
+                        .and_then(|ray| ray_to_ground(&mut raycast, &ground_q, ray, &cam))
+                        .map(|point| {
+                            cam.target_focus
+                                .translation
+                                .lerp(point, controller.zoom_cursor_strength)
+                        })
+                }
+            };
+            // Pinching the entire height of the viewport will zoom from min to max (`apply_zoom`
+            // zooms by half of `amount`)
+            controller.apply_zoom(&mut cam, 2.0 * pinch / vp_size.y, anchor);
+            send_zoom_limit(&cam, cam_entity, &mut zoom_limit);
+        }
+        if twist.is_finite() && actions.allow_rotate {
+            cam.rotate_by(twist);
         }
     }
 }
 
+/// The approximate width of the ground visible at the focus at `target_zoom`, in world units.
+fn visible_ground_width(cam: &RtsCamera, projection: Option<&Projection>) -> f32 {
+    let (fov, aspect_ratio) = match projection {
+        Some(Projection::Orthographic(p)) => return p.area.width(),
+        // `constant_height` changes the fov to match what the default fov would see at the
+        // zoom's height
+        Some(Projection::Perspective(p)) if cam.constant_height.is_none() => {
+            (p.fov, p.aspect_ratio)
+        }
+        Some(Projection::Perspective(p)) => (PerspectiveProjection::default().fov, p.aspect_ratio),
+        None => (PerspectiveProjection::default().fov, 1.0),
+    };
+    let distance = cam.height_at_zoom(cam.target_zoom) / cam.angle_at_zoom(cam.target_zoom).cos();
+    2.0 * distance * (fov / 2.0).tan() * aspect_ratio
+}
+
+/// Converts a screen space drag (in logical pixels) into a world space pan, such that the ground
+/// `distance` away from the camera will follow the cursor.
+fn drag_to_world(
+    cam: &RtsCamera,
+    mut screen_delta: Vec2,
+    vp_size: Vec2,
+    projection: &Projection,
+    distance: f32,
+) -> Vec3 {
+    let mut multiplier = 1.0;
+    match *projection {
+        Projection::Perspective(ref p) => {
+            screen_delta *= Vec2::new(p.fov * p.aspect_ratio, p.fov) / vp_size;
+            multiplier = distance;
+        }
+        Projection::Orthographic(ref p) => {
+            screen_delta *= Vec2::new(p.area.width(), p.area.height()) / vp_size;
+        }
+    }
+
+    let flat_focus = cam.flat_target_focus();
+    let mut delta = Vec3::ZERO;
+    delta += flat_focus.forward() * screen_delta.y;
+    delta += flat_focus.right() * -screen_delta.x;
+    delta *= multiplier;
+    // A zero sized viewport would give NaN or infinity, which would never recover
+    if delta.is_finite() {
+        delta
+    } else {
+        Vec3::ZERO
+    }
+}
+
 pub fn rotate(
-    mut cam_q: Query<(&mut RtsCamera, &RtsCameraControls)>,
+    controls_q: Query<(Entity, &RtsCameraControls)>,
+    mut cam_q: Query<(&mut RtsCamera, &Camera, InputState), ControllableCamera>,
     mouse_input: Res<ButtonInput<MouseButton>>,
     keys: Res<ButtonInput<KeyCode>>,
     mut mouse_motion: EventReader<MouseMotion>,
-    mut primary_window_q: Query<&mut Window, With<PrimaryWindow>>,
-    mut previous_mouse_grab_mode: Local<CursorGrabMode>,
+    mut window: ControlsWindow,
+    focus_gained: Res<FocusGained>,
+    time: Res<Time<Real>>,
+    cursor_camera: Res<CursorCamera>,
+    // The rotation when rotating started, and the mouse movement since, for `rotate_absolute_drag`
+    mut drag_start: Local<EntityHashMap<(Quat, Vec2)>>,
+    // The `min_angle` of each camera when it was first seen, for `key_reset_rotation`
+    mut initial_angle: Local<EntityHashMap<f32>>,
+    actions: Res<RtsCameraAllowedActions>,
+    // The smoothed horizontal mouse speed of each camera, for `rotate_input_smoothing`
+    mut smoothed_speed: Local<EntityHashMap<f32>>,
+    // Cameras being rotated, from when a rotate input is pressed until none are held
+    mut rotating: Local<EntityHashSet>,
+    // Cameras that locked the cursor when they started rotating
+    mut locked: Local<EntityHashSet>,
+    // Whether the window has regained focus and no mouse motion has arrived since
+    mut awaiting_motion: Local<bool>,
 ) {
-    if let Ok(mut primary_window) = primary_window_q.get_single_mut() {
-        for (mut cam, controller) in cam_q.iter_mut().filter(|(_, ctrl)| ctrl.enabled) {
-            if mouse_input.just_pressed(controller.button_rotate) && controller.lock_on_rotate {
-                *previous_mouse_grab_mode = primary_window.cursor.grab_mode;
-                primary_window.cursor.grab_mode = CursorGrabMode::Locked;
-                primary_window.cursor.visible = false;
-            }
+    // Forget cameras that have been despawned
+    drag_start.retain(|entity, _| cam_q.contains(*entity));
+    initial_angle.retain(|entity, _| cam_q.contains(*entity));
+    smoothed_speed.retain(|entity, _| cam_q.contains(*entity));
+    let mouse_delta = mouse_motion.read().map(|e| e.delta).sum::<Vec2>();
+    if focus_gained.0 {
+        *awaiting_motion = true;
+    }
+    let focus_motion = *awaiting_motion && mouse_delta != Vec2::ZERO;
+    if focus_motion {
+        *awaiting_motion = false;
+    }
+    // Cameras driven by enabled controls this frame
+    let mut driven = EntityHashSet::default();
+    for (entity, controller) in controls_q.iter().filter(|(_, ctrl)| ctrl.enabled) {
+        let cam_entity = controller.camera.unwrap_or(entity);
+        let Ok((mut cam, camera, mut input_state)) = cam_q.get_mut(cam_entity) else {
+            continue;
+        };
+        driven.insert(cam_entity);
+        let claimed = cursor_camera.claims(cam_entity);
+        // Use the camera's viewport so rotation speed is consistent for cameras that don't
+        // cover the entire window (e.g. split-screen)
+        let view_width = window
+            .view_size(camera)
+            .map_or(ROTATE_FALLBACK_VIEW_WIDTH, |size| size.x);
+
+        // (pressed, just pressed) of every button and key that rotates
+        let rotate_inputs = || {
+            controller
+                .rotate_buttons()
+                .map(|btn| (mouse_input.pressed(btn), mouse_input.just_pressed(btn)))
+                .chain(
+                    controller
+                        .key_rotate_modifier
+                        .map(|key| (keys.pressed(key), keys.just_pressed(key))),
+                )
+        };
+        let rotate_pressed = rotate_inputs().any(|(pressed, _)| pressed);
+        // Only when no other rotate input was already being held
+        let rotate_just_pressed = rotate_pressed
+            && rotate_inputs().all(|(pressed, just_pressed)| !pressed || just_pressed);
+        // Rotating only starts with a fresh press, so if the bindings change while rotating
+        // (e.g. to a button that is already held), it stops rather than carrying on
+        if rotate_just_pressed {
+            rotating.insert(cam_entity);
+        } else if !rotate_pressed {
+            rotating.remove(&cam_entity);
+        }
+        let rotate_active = rotating.contains(&cam_entity);
 
-            if mouse_input.pressed(controller.button_rotate) {
-                let mouse_delta = mouse_motion.read().map(|e| e.delta).sum::<Vec2>();
-                // Adjust based on window size, so that moving mouse entire width of window
-                // will be one half rotation (180 degrees)
-                let delta_x = mouse_delta.x / primary_window.width() * PI;
-                cam.target_focus.rotate_local_y(-delta_x);
+        if rotate_just_pressed && controller.lock_on_rotate && claimed && window.lock_cursor() {
+            locked.insert(cam_entity);
+        }
+
+        if rotate_just_pressed || !rotate_active {
+            drag_start.remove(&cam_entity);
+            smoothed_speed.remove(&cam_entity);
+        }
+
+        if !actions.allow_rotate
+            || (controller.drag_locks_rotate && controller.dragging(&mouse_input))
+        {
+            // Ignore rotation until it's allowed again
+        } else if rotate_active {
+            if let Some(input_state) = input_state.as_mut() {
+                input_state.rotating = true;
+            }
+            let mut mouse_delta = mouse_delta;
+            if (focus_motion && controller.ignore_input_on_focus) || !claimed {
+                mouse_delta = Vec2::ZERO;
+            }
+            if controller.rotate_absolute_drag {
+                let (start_rotation, total_delta) = drag_start
+                    .entry(cam_entity)
+                    .or_insert((cam.target_focus.rotation, Vec2::ZERO));
+                *total_delta += mouse_delta;
+                if cam.target_zoom >= controller.rotate_min_zoom {
+                    // Same scale as below, but from the start rather than the last frame
+                    let radians =
+                        -total_delta.x / view_width * PI * controller.rotation_zoom_scale(&cam);
+                    cam.target_focus.rotation = *start_rotation * Quat::from_rotation_y(radians);
+                }
             } else {
-                let left = if keys.pressed(controller.key_rotate_left) {
-                    1.0
-                } else {
-                    0.0
-                };
-                let right = if keys.pressed(controller.key_rotate_right) {
-                    1.0
+                let dt = time.delta_seconds();
+                let mouse_x = if controller.rotate_input_smoothing > 0.0 && dt > 0.0 {
+                    // Smooth the speed rather than the distance, so it doesn't depend on how
+                    // many frames the movement is split over
+                    let speed = smoothed_speed.entry(cam_entity).or_default();
+                    let t = 1.0 - controller.rotate_input_smoothing.powi(7).powf(dt);
+                    *speed = speed.lerp(mouse_delta.x / dt, t.clamp(0.0, 1.0));
+                    *speed * dt
                 } else {
-                    0.0
+                    mouse_delta.x
                 };
+                // Adjust based on viewport size, so that moving mouse entire width of
+                // viewport will be one half rotation (180 degrees)
+                let delta_x = mouse_x / view_width * PI;
+                controller.apply_rotate(&mut cam, -delta_x);
+            }
+        } else {
+            let left = if keys.pressed(controller.key_rotate_left) {
+                1.0
+            } else {
+                0.0
+            };
+            let right = if keys.pressed(controller.key_rotate_right) {
+                1.0
+            } else {
+                0.0
+            };
 
-                let delta = right - left;
-                if delta != 0.0 {
-                    cam.target_focus.rotate_local_y(
-                        delta / primary_window.width() * PI * controller.key_rotate_speed,
-                    );
+            let delta = right - left;
+            if delta != 0.0 {
+                if let Some(input_state) = input_state.as_mut() {
+                    input_state.rotating = true;
                 }
+                controller.apply_rotate(
+                    &mut cam,
+                    delta / view_width * PI * controller.key_rotate_speed,
+                );
+            }
+        }
+
+        let pitch_pressed = |key: Option<KeyCode>| key.is_some_and(|key| keys.pressed(key));
+        let pitch = match (
+            pitch_pressed(controller.key_pitch_up),
+            pitch_pressed(controller.key_pitch_down),
+        ) {
+            (true, false) => 1.0,
+            (false, true) => -1.0,
+            _ => 0.0,
+        };
+        if pitch != 0.0 && actions.allow_rotate {
+            cam.min_angle = (cam.min_angle
+                + pitch * controller.key_pitch_speed * time.delta_seconds())
+            // `max_angle` may have been set below zero since it was validated
+            .clamp(0.0, cam.max_angle.max(0.0));
+        }
+
+        let initial_angle = *initial_angle.entry(cam_entity).or_insert(cam.min_angle);
+        if controller
+            .key_reset_rotation
+            .is_some_and(|key| keys.just_pressed(key))
+            && actions.allow_rotate
+        {
+            cam.set_yaw(0.0);
+            if controller.key_pitch_up.is_some() || controller.key_pitch_down.is_some() {
+                cam.min_angle = initial_angle;
             }
+        }
+
+        // Only unlock once rotating has stopped. This doesn't wait for the input that started
+        // it to be released, as the bindings may have changed since.
+        if !rotate_active && locked.remove(&cam_entity) {
+            window.unlock_cursor();
+        }
+    }
+
+    // Controls that were removed or disabled while rotating can't unlock the cursor
+    // themselves. Re-added controls have to be pressed again to rotate.
+    rotating.retain(|cam_entity| driven.contains(cam_entity));
+    let was_locked = !locked.is_empty();
+    locked.retain(|cam_entity| driven.contains(cam_entity));
+    if was_locked && locked.is_empty() {
+        window.unlock_cursor();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::SystemState;
+    use bevy::window::WindowResolution;
+
+    const DT: f32 = 0.1;
+
+    fn assert_near(a: Vec3, b: Vec3) {
+        assert!(a.distance(b) < 1e-4, "expected {b}, got {a}");
+    }
+
+    #[test]
+    fn intent_rotates_before_panning() {
+        let controls = RtsCameraControls::default();
+        let intent = RtsCameraIntent {
+            pan: Vec2::Y,
+            zoom: 1.0,
+            rotate: TAU / 4.0,
+        };
+
+        let mut combined = RtsCamera::default();
+        controls.apply_intent(&mut combined, &intent, DT);
 
-            if mouse_input.just_released(controller.button_rotate) {
-                primary_window.cursor.grab_mode = *previous_mouse_grab_mode;
-                primary_window.cursor.visible = true;
+        let mut separate = RtsCamera::default();
+        controls.apply_rotate(&mut separate, intent.rotate);
+        controls.apply_pan(&mut separate, intent.pan, DT);
+        controls.apply_zoom(&mut separate, intent.zoom, None);
+
+        assert_near(
+            combined.target_focus.translation,
+            separate.target_focus.translation,
+        );
+        assert_eq!(combined.target_zoom, separate.target_zoom);
+        // Rotated a quarter turn counterclockwise, so forward is now -X
+        let pan = combined.target_focus.translation;
+        assert!(pan.x < 0.0 && pan.z.abs() < 1e-4, "panned to {pan}");
+    }
+
+    #[test]
+    fn intent_is_stable() {
+        let controls = RtsCameraControls::default();
+        let intent = RtsCameraIntent {
+            pan: Vec2::new(1.0, 1.0),
+            zoom: 0.5,
+            rotate: 0.3,
+        };
+        let run = || {
+            let mut cam = RtsCamera::default();
+            for _ in 0..10 {
+                controls.apply_intent(&mut cam, &intent, DT);
             }
+            (cam.target_focus, cam.target_zoom)
+        };
+        assert_eq!(run(), run());
+    }
+
+    #[test]
+    fn empty_intent_does_nothing() {
+        let controls = RtsCameraControls::default();
+        let mut cam = RtsCamera::default();
+        controls.apply_intent(&mut cam, &RtsCameraIntent::default(), DT);
+        assert_eq!(cam.target_focus, RtsCamera::default().target_focus);
+        assert_eq!(cam.target_zoom, RtsCamera::default().target_zoom);
+    }
+
+    #[test]
+    fn intent_pans_zooms_and_rotates() {
+        let controls = RtsCameraControls::default();
+        let mut cam = RtsCamera::default();
+        controls.apply_intent(
+            &mut cam,
+            &RtsCameraIntent {
+                pan: Vec2::X,
+                ..default()
+            },
+            DT,
+        );
+        let pan = cam.target_focus.translation;
+        assert!(pan.x > 0.0 && pan.z == 0.0, "should pan right, got {pan}");
+
+        controls.apply_intent(
+            &mut cam,
+            &RtsCameraIntent {
+                zoom: 1.0,
+                ..default()
+            },
+            DT,
+        );
+        assert!(cam.target_zoom > 0.0);
+
+        controls.apply_intent(
+            &mut cam,
+            &RtsCameraIntent {
+                rotate: 1.0,
+                ..default()
+            },
+            DT,
+        );
+        assert!(cam.target_focus.rotation != Quat::IDENTITY);
+    }
+
+    #[test]
+    fn intent_pan_is_clamped_to_pan_speed() {
+        let controls = RtsCameraControls::default();
+        let pan = |pan: Vec2| {
+            let mut cam = RtsCamera::default();
+            controls.apply_intent(&mut cam, &RtsCameraIntent { pan, ..default() }, DT);
+            cam.target_focus.translation
+        };
+        assert_near(pan(Vec2::Y * 10.0), pan(Vec2::Y));
+        // Shorter vectors pan slower
+        assert_near(pan(Vec2::Y * 0.5), pan(Vec2::Y) * 0.5);
+    }
+
+    /// A world with a primary window for each of `cursors`, with the cursor at that position.
+    fn window_world(cursors: &[Option<Vec2>]) -> World {
+        let mut world = World::new();
+        for cursor in cursors {
+            let mut window = Window {
+                resolution: WindowResolution::new(800.0, 600.0),
+                ..default()
+            };
+            window.set_cursor_position(*cursor);
+            world.spawn((window, PrimaryWindow));
+        }
+        world
+    }
+
+    #[test]
+    fn controls_window_without_a_window() {
+        let mut world = window_world(&[]);
+        let mut state = SystemState::<ControlsWindow>::new(&mut world);
+        let mut window = state.get_mut(&mut world);
+        assert_eq!(window.size(), None);
+        assert_eq!(window.cursor_position(), None);
+        assert_eq!(window.view_size(&Camera::default()), None);
+        assert!(!window.cursor_hidden());
+        assert!(!window.lock_cursor());
+    }
+
+    #[test]
+    fn controls_window_resolves_the_primary_window() {
+        let mut world = window_world(&[Some(Vec2::new(100.0, 200.0))]);
+        let mut state = SystemState::<ControlsWindow>::new(&mut world);
+        let window = state.get_mut(&mut world);
+        assert_eq!(window.size(), Some(Vec2::new(800.0, 600.0)));
+        assert_eq!(window.cursor_position(), Some(Vec2::new(100.0, 200.0)));
+        // Cameras without a viewport use the whole window
+        assert_eq!(
+            window.view_size(&Camera::default()),
+            Some(Vec2::new(800.0, 600.0))
+        );
+        // As do cameras whose viewport hasn't been worked out by the renderer yet
+        let camera = Camera {
+            viewport: Some(bevy::render::camera::Viewport {
+                physical_size: UVec2::new(100, 100),
+                ..default()
+            }),
+            ..default()
+        };
+        assert_eq!(window.view_size(&camera), Some(Vec2::new(800.0, 600.0)));
+    }
+
+    #[test]
+    fn controls_window_ignores_a_cursor_outside_the_window() {
+        for cursor in [Vec2::new(-1.0, 200.0), Vec2::new(100.0, 601.0)] {
+            let mut world = window_world(&[Some(cursor)]);
+            let mut state = SystemState::<ControlsWindow>::new(&mut world);
+            assert_eq!(state.get_mut(&mut world).cursor_position(), None);
         }
     }
+
+    #[test]
+    fn controls_window_needs_exactly_one_primary_window() {
+        let cursor = Some(Vec2::new(100.0, 200.0));
+        let mut world = window_world(&[cursor, cursor]);
+        let mut state = SystemState::<ControlsWindow>::new(&mut world);
+        let window = state.get_mut(&mut world);
+        assert_eq!(window.size(), None);
+        assert_eq!(window.cursor_position(), None);
+    }
+
+    #[test]
+    fn controls_window_unlock_restores_the_grab_mode() {
+        let mut world = window_world(&[None]);
+        let mut window_q = world.query::<&mut Window>();
+        window_q.single_mut(&mut world).cursor.grab_mode = CursorGrabMode::Confined;
+        let mut state = SystemState::<ControlsWindow>::new(&mut world);
+
+        assert!(state.get_mut(&mut world).lock_cursor());
+        assert!(state.get_mut(&mut world).cursor_hidden());
+        let cursor = &window_q.single(&world).cursor;
+        assert_eq!(cursor.grab_mode, CursorGrabMode::Locked);
+        assert!(!cursor.visible);
+
+        state.get_mut(&mut world).unlock_cursor();
+        let cursor = &window_q.single(&world).cursor;
+        assert_eq!(cursor.grab_mode, CursorGrabMode::Confined);
+        assert!(cursor.visible);
+    }
+
+    #[test]
+    fn input_goes_to_the_topmost_viewport_under_it() {
+        let left = Entity::from_raw(1);
+        let right = Entity::from_raw(2);
+        let overlay = Entity::from_raw(3);
+        let cameras = [
+            (left, 0, Some(Rect::new(0.0, 0.0, 400.0, 600.0))),
+            (right, 0, Some(Rect::new(400.0, 0.0, 800.0, 600.0))),
+            // Drawn on top of the right half, over its bottom corner
+            (overlay, 1, Some(Rect::new(600.0, 400.0, 800.0, 600.0))),
+            // Not rendered yet, so it has no viewport
+            (Entity::from_raw(4), 2, None),
+        ];
+        let at = |x: f32, y: f32| topmost_camera_at(Vec2::new(x, y), cameras);
+        assert_eq!(at(100.0, 300.0), Some(left));
+        assert_eq!(at(500.0, 300.0), Some(right));
+        assert_eq!(at(700.0, 500.0), Some(overlay));
+        assert_eq!(at(900.0, 300.0), None);
+    }
+
+    #[test]
+    fn pop_override_restores_the_whole_profile() {
+        let mut controls = RtsCameraControls::default();
+        let before = controls.profile();
+        controls.push_override(RtsCameraControlProfile {
+            cursor_lean: 0.2,
+            drag_threshold: 10.0,
+            zoom_fine_factor: 0.5,
+            ..before
+        });
+        assert_eq!(controls.cursor_lean, 0.2);
+        assert_eq!(controls.drag_threshold, 10.0);
+        assert_eq!(controls.zoom_fine_factor, 0.5);
+        controls.pop_override();
+        assert_eq!(controls.profile(), before);
+    }
+
+    #[test]
+    fn pan_with_zero_delta_stays_put() {
+        let controls = RtsCameraControls::default();
+        let mut cam = RtsCamera::default();
+        controls.apply_pan(&mut cam, Vec2::ZERO, DT);
+        assert_eq!(cam.target_focus.translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn pan_while_paused_stays_put() {
+        let controls = RtsCameraControls::default();
+        let mut cam = RtsCamera::default();
+        controls.apply_pan(&mut cam, Vec2::Y, 0.0);
+        assert_eq!(cam.target_focus.translation, Vec3::ZERO);
+    }
+
+    #[test]
+    fn pan_ignores_non_finite_input() {
+        let controls = RtsCameraControls {
+            pan_speed_mode: PanSpeedMode::ProportionalToHeight,
+            ..default()
+        };
+        // A `height_max` of `0.0` divides by zero when working out the pan speed
+        let mut cam = RtsCamera {
+            height_max: 0.0,
+            ..default()
+        };
+        controls.apply_pan(&mut cam, Vec2::Y, DT);
+        assert_eq!(cam.target_focus.translation, Vec3::ZERO);
+
+        let mut cam = RtsCamera::default();
+        controls.apply_pan(&mut cam, Vec2::NAN, DT);
+        assert_eq!(cam.target_focus.translation, Vec3::ZERO);
+    }
 }
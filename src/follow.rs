@@ -0,0 +1,89 @@
+use std::marker::PhantomData;
+
+use crate::{RtsCamera, RtsCameraSystemSet};
+use bevy::prelude::*;
+
+/// Plugin that makes cameras with `RtsCameraFollowGroup<M>` follow all entities with the marker
+/// component `M`. Add one of these for each marker you want to follow.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_rts_camera::{RtsCamera, RtsCameraFollowGroup, RtsCameraFollowGroupPlugin, RtsCameraPlugin};
+/// #[derive(Component)]
+/// struct MyArmy;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugins(RtsCameraPlugin)
+///         .add_plugins(RtsCameraFollowGroupPlugin::<MyArmy>::default())
+///         .add_systems(Startup, setup)
+///         .run();
+/// }
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         Camera3dBundle::default(),
+///         RtsCamera::default(),
+///         RtsCameraFollowGroup::<MyArmy>::default(),
+///     ));
+/// }
+/// ```
+pub struct RtsCameraFollowGroupPlugin<M: Component> {
+    _marker: PhantomData<M>,
+}
+
+impl<M: Component> Default for RtsCameraFollowGroupPlugin<M> {
+    fn default() -> Self {
+        RtsCameraFollowGroupPlugin {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<M: Component> Plugin for RtsCameraFollowGroupPlugin<M> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, follow_group::<M>.before(RtsCameraSystemSet));
+    }
+}
+
+/// Add this to an `RtsCamera` to keep it focused on the average position (centroid) of all
+/// entities with the marker component `M`, e.g. to follow the player's whole army. The camera
+/// doesn't move while there are no such entities. Requires `RtsCameraFollowGroupPlugin<M>`.
+#[derive(Component)]
+pub struct RtsCameraFollowGroup<M: Component> {
+    /// The fastest `target_focus` can move towards the group, in world units per second. Movement
+    /// slower than this is followed exactly, but if the group jumps (e.g. a unit teleports), the
+    /// camera glides over instead of cutting to it.
+    /// Defaults to `f32::INFINITY` (always follow exactly).
+    pub max_follow_speed: f32,
+    _marker: PhantomData<M>,
+}
+
+impl<M: Component> Default for RtsCameraFollowGroup<M> {
+    fn default() -> Self {
+        RtsCameraFollowGroup {
+            max_follow_speed: f32::INFINITY,
+            _marker: PhantomData,
+        }
+    }
+}
+
+fn follow_group<M: Component>(
+    mut cam_q: Query<(&mut RtsCamera, &RtsCameraFollowGroup<M>)>,
+    group_q: Query<&GlobalTransform, With<M>>,
+    time: Res<Time<Real>>,
+) {
+    let (sum, count) = group_q.iter().fold((Vec3::ZERO, 0), |(sum, count), tfm| {
+        (sum + tfm.translation(), count + 1)
+    });
+    if count == 0 {
+        return;
+    }
+    let centroid = sum / count as f32;
+    for (mut cam, follow) in cam_q.iter_mut() {
+        let current = cam.target_focus_xz();
+        let max_step = follow.max_follow_speed * time.delta_seconds();
+        cam.set_target_focus_xz(current + (centroid.xz() - current).clamp_length_max(max_step));
+    }
+}
@@ -3,17 +3,45 @@
 
 use std::f32::consts::TAU;
 
-use bevy::math::bounding::Aabb2d;
+use bevy::ecs::entity::{EntityHashMap, EntityHashSet};
+use bevy::math::bounding::{Aabb2d, BoundingCircle};
 use bevy::prelude::*;
-use bevy_mod_raycast::prelude::{IntersectionData, Raycast, RaycastSettings};
+use bevy::render::primitives::Aabb;
+use bevy_mod_raycast::prelude::{CursorRay, IntersectionData, Raycast, RaycastSettings};
 
-pub use controller::RtsCameraControls;
+pub use controller::{
+    EdgePanWidthUnit, PanSpeedMode, PanSpeedUnit, RtsCameraAllowedActions, RtsCameraControlProfile,
+    RtsCameraControlSchemes, RtsCameraControls, RtsCameraControlsSystemSet, RtsCameraInputDisabled,
+    RtsCameraInputState, RtsCameraIntent, RtsCameraPanIntent, RtsCameraZoomLimit,
+    RtsCameraZoomTarget, ZoomAnchor, ZoomCursorMiss, ZoomWhileRotating,
+};
+pub use follow::{RtsCameraFollowGroup, RtsCameraFollowGroupPlugin};
+pub use mirror::RtsCameraMirror;
+#[cfg(feature = "ron")]
+pub use settings::{load_controls, save_controls};
+pub use transition::{RtsCameraEasing, RtsCameraTransition, RtsCameraTransitionFinished};
 
 use crate::controller::RtsCameraControlsPlugin;
+use crate::mirror::mirror_focus;
+use crate::transition::RtsCameraTransitionPlugin;
 
 mod controller;
+mod follow;
+mod mirror;
+#[cfg(feature = "ron")]
+mod settings;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod transition;
 
 const MAX_ANGLE: f32 = TAU / 5.0;
+/// The camera angle is always kept below this, so it can never reach or pass the horizon (which
+/// would flip the camera upside down).
+const ANGLE_LIMIT: f32 = TAU / 4.0 - 0.001;
+/// How long in seconds the camera takes to tilt for `flatten_pitch_while_panning`.
+const FLATTEN_PITCH_TIME: f32 = 0.3;
+/// How far below a `Ceiling` the camera stops when zooming out.
+const CEILING_CLEARANCE: f32 = 0.1;
 
 /// Bevy plugin that provides RTS camera controls.
 /// # Example
@@ -31,17 +59,49 @@ pub struct RtsCameraPlugin;
 
 impl Plugin for RtsCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(RtsCameraControlsPlugin)
-            .add_systems(PreUpdate, initialize)
+        app.add_plugins((RtsCameraControlsPlugin, RtsCameraTransitionPlugin))
+            .init_resource::<RtsCameraActive>()
+            .add_event::<RtsCameraSettled>()
+            .configure_sets(
+                Update,
+                RtsCameraSystemSet.run_if(resource_equals(RtsCameraActive(true))),
+            )
+            .add_systems(
+                PreUpdate,
+                (
+                    initialize,
+                    reset_pan_delta,
+                    update_ground_plane,
+                    update_cursor_ground,
+                )
+                    .chain(),
+            )
+            .configure_sets(
+                FixedUpdate,
+                RtsCameraSystemSet.run_if(resource_equals(RtsCameraActive(true))),
+            )
+            .add_systems(
+                FixedUpdate,
+                fixed_move_towards_target.in_set(RtsCameraSystemSet),
+            )
             .add_systems(
                 Update,
                 (
+                    mirror_focus,
                     follow_ground,
+                    limit_zoom_to_ceiling,
+                    level_target_focus,
+                    auto_rotate,
+                    stable_framing,
+                    limit_zoom_to_bounds,
                     snap_to_target,
+                    flatten_pitch,
                     dynamic_angle,
                     move_towards_target,
                     apply_bounds,
                     update_camera_transform,
+                    update_projection,
+                    detect_settled,
                 )
                     .chain()
                     .in_set(RtsCameraSystemSet),
@@ -49,12 +109,48 @@ impl Plugin for RtsCameraPlugin {
     }
 }
 
-/// System set containing all the systems that control the RTS camera.
+/// System set containing all the systems that control the RTS camera. Runs in `Update`.
 /// If you want to control the camera manually in any way (e.g. snapping to a specific location),
-/// you should run that before this system set.
+/// you should run that before this system set, i.e. with `.before(RtsCameraSystemSet)` in
+/// `Update`. Changes to `target_focus` and `target_zoom` made before this set are always used the
+/// same frame. To override the built-in controls rather than add to them, also run after
+/// `RtsCameraControlsSystemSet`.
+/// The camera's `Transform` is updated by the end of this set, so systems that run after it will
+/// see where the camera is this frame. Note that `GlobalTransform` is not updated until transform
+/// propagation in `PostUpdate`, so if you need that (e.g. for `Camera::world_to_viewport`), run
+/// your system in `PostUpdate` after `TransformSystem::TransformPropagate` instead. See the
+/// `ui_follow` example.
+/// The camera is moved once per rendered frame (using real time), rather than in `FixedUpdate`.
+/// If you set `target_focus` from `FixedUpdate` (e.g. to follow a unit that is simulated there),
+/// smoothing will usually hide the fixed steps, otherwise see `RtsCamera::interpolate_fixed`, which
+/// moves the camera in this set in `FixedUpdate` instead.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
 pub struct RtsCameraSystemSet;
 
+/// Resource that controls whether the RTS camera systems (including the controls) run at all.
+/// Set to `RtsCameraActive(false)` to freeze all cameras in place, e.g. while a pause menu or
+/// loading screen is shown.
+/// Defaults to `RtsCameraActive(true)`.
+#[derive(Resource, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RtsCameraActive(pub bool);
+
+/// Event sent once when a camera stops moving, i.e. it was moving towards its targets (after
+/// input, a `RtsCameraTransition`, a bookmark, etc.) and has now reached them within
+/// `RtsCamera::settle_epsilon`. Useful for 'camera is idle' logic like re-enabling input. It
+/// isn't sent again until the camera has moved and stopped again, and a camera that is still in
+/// the middle of a `RtsCameraTransition` doesn't count as stopped.
+#[derive(Event, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RtsCameraSettled {
+    /// The camera entity that stopped moving.
+    pub entity: Entity,
+}
+
+impl Default for RtsCameraActive {
+    fn default() -> Self {
+        RtsCameraActive(true)
+    }
+}
+
 /// Marks a camera to be used as an RTS camera.
 /// Only one instance of this component should exist at any given moment.
 /// Typically you'll add this alongside a `Camera3dBundle`.
@@ -90,11 +186,35 @@ pub struct RtsCamera {
     /// The bounds in which the camera is constrained, along the XZ plane of `target_focus`. This
     /// prevents panning past these limits. Imagine looking directly down relative to `target_focus`
     /// and the XZ plane corresponds XY of the Vec2s, except +Y is up/forward (-Z).
-    /// Defaults to `Aabb2d::new(Vec2::ZERO, Vec2::new(20.0, 20.0))` (i.e. can move 20.0 in any
-    /// direction starting at world center).
-    pub bounds: Aabb2d,
+    /// Defaults to `RtsCameraBounds::Rect(Aabb2d::new(Vec2::ZERO, Vec2::new(20.0, 20.0)))` (i.e. can
+    /// move 20.0 in any direction starting at world center).
+    pub bounds: RtsCameraBounds,
+    /// Whether the focus is allowed at a point on the ground (where X is world X and Y is world Z),
+    /// for maps that aren't a simple rectangle or circle, e.g. by checking a grid of walkable
+    /// cells. This is checked on top of `bounds`. When `target_focus` moves somewhere that isn't
+    /// allowed, it slides along the edge if it can, and otherwise is moved back to the nearest
+    /// allowed point between there and where it was last allowed.
+    /// Defaults to `None` (anywhere within `bounds` is allowed).
+    pub navigable: Option<fn(Vec2) -> bool>,
+    /// Whether to limit how far the camera can zoom out, such that the visible area is no wider
+    /// than the larger side of `bounds`. This stops you from seeing lots of empty space beyond the
+    /// edge of the map. Only applies to perspective projections, and is approximate when the
+    /// camera is tilted.
+    /// Defaults to `false`.
+    pub limit_zoom_to_bounds: bool,
+    /// What to do along an axis where `bounds` is smaller than the visible area, where keeping
+    /// the focus within the bounds still leaves empty space showing on one side or the other.
+    /// Defaults to `SmallBoundsBehavior::Clamp`.
+    pub small_bounds_behavior: SmallBoundsBehavior,
+    /// Whether to zoom to keep the visible width of the ground the same when the viewport's
+    /// aspect ratio changes (e.g. the window is resized), instead of the view popping wider or
+    /// narrower. Only applies to perspective projections.
+    /// Defaults to `false`.
+    pub stable_framing_on_resize: bool,
     /// The current angle in radians of the camera, where a value of `0.0` is looking directly down
     /// (-Y), and a value of `TAU / 4.0` (90 degrees) is looking directly forward.
+    /// When `dynamic_angle` is enabled, this is calculated from the (smoothed) `zoom`, so the
+    /// angle always matches the camera's height.
     /// If you want to customise the angle, set `min_angle` instead.
     /// Defaults to 25 degrees.
     pub angle: f32,
@@ -110,16 +230,79 @@ pub struct RtsCamera {
     /// If you want to customise the angle, this is what you want to change.
     /// Defaults to 25 degrees.
     pub min_angle: f32,
+    /// How the zoom level maps to the camera's height.
+    /// Defaults to `ZoomCurve::Linear`.
+    pub zoom_curve: ZoomCurve,
     /// Whether the camera should increase its angle the more you zoom in, so you can see
     /// characters up close from a sideways view instead of top down.
-    /// If this is
+    /// If this is disabled, the camera stays at `min_angle`. Only used with `PitchMode::FromZoom`.
     /// Defaults to `true`.
     pub dynamic_angle: bool,
-    /// The amount of smoothing applied to the camera movement. Should be a value between `0.0` and
-    /// `1.0`. Set to `0.0` to disable smoothing. `1.0` is infinite smoothing (the camera won't
-    /// move).
+    /// What controls the camera's angle.
+    /// Defaults to `PitchMode::FromZoom`.
+    pub pitch_mode: PitchMode,
+    /// How far to tilt the camera towards looking straight down while it's being panned, from
+    /// `0.0` (not at all) to `1.0` (fully top down), for a clearer overview while moving. It
+    /// smoothly tilts back once panning stops. Applies to panning with the built-in controls or
+    /// `pan_by`.
+    /// Defaults to `0.0`.
+    pub flatten_pitch_while_panning: f32,
+    /// How much of `flatten_pitch_while_panning` is currently applied, from `0.0` to `1.0`.
+    /// Updated automatically.
+    /// Defaults to `0.0`.
+    pub pan_flatten: f32,
+    /// The angle of the camera at full zoom (min height) when `dynamic_angle` is enabled. This is
+    /// also the largest angle the camera can have.
+    /// The angle is always kept between `0.0` (looking straight down) and just under `TAU / 4.0`
+    /// (looking at the horizon), regardless of `min_angle` and `max_angle`.
+    /// Defaults to 72 degrees.
+    pub max_angle: f32,
+    /// The amount of smoothing applied to the focus moving (panning and rotating). Should be a
+    /// value between `0.0` and `1.0`. Set to `0.0` to disable smoothing. `1.0` is infinite
+    /// smoothing (the focus won't move). See also `zoom_smoothing` and `set_smoothing`.
+    /// Defaults to `0.3`.
+    pub focus_smoothing: f32,
+    /// The amount of smoothing applied to zooming (and the angle changing), e.g. lower than
+    /// `focus_smoothing` for snappy zooming but floaty panning. Should be a value between `0.0`
+    /// and `1.0`, like `focus_smoothing`. With `RtsCameraSmoothing::Spring`, the spring's
+    /// `stiffness` and `damping` apply to the focus as they are, and the zoom spring is instead
+    /// sped up or slowed down by how much less or more smoothing this is than `focus_smoothing`.
     /// Defaults to `0.3`.
-    pub smoothness: f32,
+    pub zoom_smoothing: f32,
+    /// The maximum acceleration of zooming, in zoom levels per second squared, so zooming speeds
+    /// up and slows down gradually for a weightier feel. It always slows down in time to stop at
+    /// `target_zoom`, so it never overshoots. Only applies to `RtsCameraSmoothing::Exponential`.
+    /// Defaults to `0.0` (unlimited).
+    pub zoom_accel: f32,
+    /// How the camera moves towards its targets.
+    /// Defaults to `RtsCameraSmoothing::Exponential`.
+    pub smoothing: RtsCameraSmoothing,
+    /// How close in world units the focus (or the camera's height, for zoom) has to be to its
+    /// target for the camera to jump straight there instead of smoothing. This makes small nudges
+    /// crisp, while large movements stay smooth.
+    /// Defaults to `0.0` (always smooth).
+    pub smoothing_snap_threshold: f32,
+    /// How close the camera has to be to all of its targets (see `is_settled`) to count as having
+    /// stopped moving, for `RtsCameraSettled`.
+    /// Defaults to `0.001`.
+    pub settle_epsilon: f32,
+    /// The current velocity of `focus`, `zoom` and `angle` when using
+    /// `RtsCameraSmoothing::Spring`, and of `zoom` when using `zoom_accel`.
+    /// Updated automatically.
+    /// Defaults to `RtsCameraVelocity::default()` (not moving).
+    pub velocity: RtsCameraVelocity,
+    /// Whether to move the camera towards its targets in `FixedUpdate` (see `Time<Fixed>`)
+    /// rather than once per frame, drawing it between where it was as of the last two fixed
+    /// timesteps. Useful when the rest of your game is simulated in `FixedUpdate` and the camera's
+    /// movement should step in time with it, at the cost of lagging up to one fixed timestep
+    /// behind.
+    /// Defaults to `false`.
+    pub interpolate_fixed: bool,
+    /// `focus`, `zoom` and `angle` as of the last two fixed timesteps, used by
+    /// `interpolate_fixed`.
+    /// Updated automatically.
+    /// Defaults to `None`.
+    pub fixed_state: Option<RtsCameraFixedState>,
     /// The current focus of the camera, including the orientation (which way is forward). The
     /// camera's actual transform is calculated based on this transform.
     /// Updated automatically.
@@ -134,52 +317,912 @@ pub struct RtsCamera {
     /// set the starting position.
     /// Defaults to `Transform::IDENTITY`.
     pub target_focus: Transform,
+    /// Whether `target_focus` may be tilted (rolled or pitched). When disabled, any tilt (e.g. from
+    /// editing `target_focus.rotation` directly) is removed every frame, keeping only its rotation
+    /// around the Y axis, so the horizon stays level. Use `min_angle` to tilt the camera instead.
+    /// Defaults to `false`.
+    pub allow_roll: bool,
+    /// How fast the camera rotates on its own in radians per second, e.g. for a title screen
+    /// backdrop. Positive values rotate counterclockwise (looking from above). This pauses while
+    /// the camera is being rotated some other way (e.g. by the controls), and resumes after
+    /// `auto_rotate_delay`.
+    /// Defaults to `0.0` (disabled).
+    pub auto_rotate_speed: f32,
+    /// How long in seconds auto rotation waits to resume after the camera has been rotated some
+    /// other way. See `auto_rotate_speed`.
+    /// Defaults to `2.0`.
+    pub auto_rotate_delay: f32,
     /// The current zoom level, between `0.0` and `1.0`, where 0 is no zoom (`height_max`), and 1 is
     /// max zoom (`height_min`).
+    /// This is the smoothed zoom level that is actually rendered, which moves towards
+    /// `target_zoom` over time (like `focus` does with `target_focus`). Read this if you want to
+    /// react to the visible zoom level, e.g. for LOD or UI scale.
     /// Typically you won't need to set this manually, even if you implement your own controls.
     /// Set `target_zoom` instead.
     /// Defaults to `0.0`.
     pub zoom: f32,
-    /// The target zoom level. Used to implement zoom smoothing.
+    /// The target zoom level, which `zoom` will smoothly move towards.
     /// Updated automatically when using `RtsCameraControls`, but should be updated manually
     /// if you implement your own controls. You can also change this when adding this component to
     /// set the starting zoom.
     /// Defaults to `0.0`.
     pub target_zoom: f32,
+    /// The lowest value `target_zoom` can be zoomed out to. Raise this to narrow the usable zoom
+    /// range without changing `height_max`. Values below `0.0` extrapolate past `height_max`, and
+    /// `f32::NEG_INFINITY` disables the limit.
+    /// Defaults to `0.0`.
+    pub zoom_min: f32,
+    /// The highest value `target_zoom` can be zoomed in to. Lower this to narrow the usable zoom
+    /// range without changing `height_min`. Values above `1.0` extrapolate past `height_min`, and
+    /// `f32::INFINITY` disables the limit.
+    /// Defaults to `1.0`.
+    pub zoom_max: f32,
+    /// Saved zoom levels, e.g. for an 'overview zoom' button. See `save_zoom` and `recall_zoom`.
+    /// Defaults to `[None; 10]`.
+    pub zoom_bookmarks: [Option<f32>; 10],
     /// Whether the camera should snap to `target_focus` and `target_zoom`. Will be set to
     /// `false` after one frame. Useful if you want to lock the camera to a specific target (e.g.
     /// to follow a unit), by setting `target_focus` and setting this to `true` on every frame.
     /// Defaults to `false`.
     pub snap: bool,
+    /// Whether the camera is paused in place. While frozen, the camera doesn't move towards its
+    /// targets (including `snap`), but the targets can still change, and it will carry on
+    /// smoothly towards them once unfrozen. See `freeze` and `unfreeze`.
+    /// Defaults to `false`.
+    pub frozen: bool,
+    /// The height of the ground when there are no `Ground` entities under the camera. Useful if
+    /// your ground is a flat plane, as an alternative to marking it with `Ground`.
+    /// Defaults to `0.0`.
+    pub ground_height: f32,
+    /// An entity whose transform defines the ground when there are no `Ground` entities under the
+    /// camera, instead of `ground_height`. The ground is the plane through the entity's position,
+    /// facing its local up, so it can be tilted or moving, e.g. the deck of a ship. The focus
+    /// stays on this plane, and the cursor and zoom anchor are found on it, but panning still
+    /// moves the focus horizontally. Ignored if the entity has no `GlobalTransform`, or is
+    /// vertical.
+    /// Defaults to `None`.
+    pub ground_plane: Option<Entity>,
+    /// The `GlobalTransform` of `ground_plane`, as of the start of this frame.
+    /// Updated automatically.
+    /// Defaults to `GlobalTransform::IDENTITY`.
+    pub ground_plane_transform: GlobalTransform,
+    /// Whether to keep the height of `target_focus` on the ground under it every frame (the
+    /// nearest `Ground` entity, or else `ground_plane` or `ground_height`), so code that reads
+    /// the focus always gets a sensible height. Disable this to control the focus height
+    /// yourself, e.g. for a flying camera.
+    /// Defaults to `true`.
+    pub follow_ground: bool,
+    /// The maximum world height (Y) of the camera, regardless of zoom. When the camera would go
+    /// above this, it is lowered and tilted so it still looks at the focus. Useful for indoor
+    /// scenes where the camera shouldn't go through the ceiling. Unlike `height_max`, this is a
+    /// world height rather than a height above the focus.
+    /// Defaults to `f32::INFINITY` (no limit).
+    pub max_height: f32,
+    /// When set, the camera stays at this height above the focus, and zooming changes the
+    /// projection instead (`fov` for perspective, `scale` for orthographic), so that the visible
+    /// area matches what it would be at the height given by `height_min`/`height_max`. This
+    /// overrides the `fov` or `scale` of the camera's `Projection`.
+    /// Defaults to `None`.
+    pub constant_height: Option<f32>,
+    /// The `scale` of an orthographic projection at `1.0` zoom (fully zoomed in). Moving an
+    /// orthographic camera doesn't change how much it can see, so zoom changes the scale between
+    /// `ortho_scale_max` and this (following `zoom_curve`) instead. This overrides the `scale` of
+    /// the camera's `OrthographicProjection`, unless `constant_height` is set.
+    /// Defaults to `1.0` (same as `ortho_scale_max`, so zoom doesn't change the scale).
+    pub ortho_scale_min: f32,
+    /// The `scale` of an orthographic projection at `0.0` zoom (fully zoomed out). See
+    /// `ortho_scale_min`.
+    /// Defaults to `1.0`.
+    pub ortho_scale_max: f32,
+    /// Where on screen the focus appears, as a fraction of the viewport size from its center,
+    /// where +X is right and +Y is up. E.g. `Vec2::new(0.0, 0.15)` moves the focus up by 15% of
+    /// the viewport's height, which is useful if a HUD covers the bottom of the screen. The camera
+    /// is moved (not rotated) to achieve this, so panning and zooming are unaffected.
+    /// Defaults to `Vec2::ZERO`.
+    pub screen_offset: Vec2,
+    /// Moves the camera by this many world units along its own right (+X) and up (+Y)
+    /// directions, rather than world axes, without affecting the focus or smoothing. Useful for
+    /// camera shake, as a vertical offset always looks vertical on screen, whatever the camera's
+    /// angle and heading.
+    /// Defaults to `Vec2::ZERO`.
+    pub view_offset: Vec2,
+    /// How far the view is leaned towards the cursor by `RtsCameraControls.cursor_lean`, in world
+    /// units on the ground plane (where Y is world Z). This is added on top of the focus when
+    /// positioning the camera, so it never moves `target_focus` and isn't limited by `bounds`.
+    /// Updated automatically.
+    /// Defaults to `Vec2::ZERO`.
+    pub lean: Vec2,
+    /// The nearest `Ground` entity under the mouse cursor, and where the cursor ray hit it. When
+    /// several ground meshes overlap (e.g. an elevated platform above terrain), the one closest to
+    /// the camera is chosen.
+    /// Updated automatically.
+    /// Defaults to `None`.
+    pub cursor_ground: Option<GroundHit>,
+    /// How far `target_focus` has been panned this frame in world units, by the built-in controls
+    /// or `pan_by`. Useful for moving things along with the camera, e.g. a parallax background.
+    /// This doesn't include the focus moving because of zooming, bounds or `snap`.
+    /// Reset to `Vec3::ZERO` in `PreUpdate` each frame, and updated automatically.
+    /// Defaults to `Vec3::ZERO`.
+    pub last_pan_delta: Vec3,
+    /// Analog pan input for this frame, relative to the camera's heading (where +Y is forward),
+    /// with a length of up to `1.0`. See `set_pan_input`.
+    /// Reset to `Vec2::ZERO` by `RtsCameraControls` once used.
+    /// Defaults to `Vec2::ZERO`.
+    pub pan_input: Vec2,
+    /// Which edges of the screen `RtsCameraControls` is edge panning at this frame, e.g. for
+    /// showing a directional scroll cursor.
+    /// Updated automatically.
+    /// Defaults to `RtsCameraEdgePan::default()` (no edges).
+    pub edge_pan: RtsCameraEdgePan,
+    /// The most zoomed out `target_zoom` allowed by the nearest `Ceiling` above the focus, or
+    /// `zoom_min` if there isn't one. Useful for showing the effective zoom range in UI.
+    /// Updated automatically.
+    /// Defaults to `0.0`.
+    pub ceiling_zoom_min: f32,
 }
 
 impl Default for RtsCamera {
     fn default() -> Self {
         RtsCamera {
-            bounds: Aabb2d::new(Vec2::ZERO, Vec2::new(20.0, 20.0)),
+            bounds: Aabb2d::new(Vec2::ZERO, Vec2::new(20.0, 20.0)).into(),
+            navigable: None,
+            limit_zoom_to_bounds: false,
+            small_bounds_behavior: SmallBoundsBehavior::Clamp,
+            stable_framing_on_resize: false,
             height_min: 2.0,
             height_max: 30.0,
             angle: 20.0f32.to_radians(),
             target_angle: 20.0f32.to_radians(),
             min_angle: 20.0f32.to_radians(),
+            zoom_curve: ZoomCurve::Linear,
             dynamic_angle: true,
-            smoothness: 0.3,
+            pitch_mode: PitchMode::FromZoom,
+            flatten_pitch_while_panning: 0.0,
+            pan_flatten: 0.0,
+            max_angle: MAX_ANGLE,
+            focus_smoothing: 0.3,
+            zoom_smoothing: 0.3,
+            zoom_accel: 0.0,
+            smoothing: RtsCameraSmoothing::Exponential,
+            smoothing_snap_threshold: 0.0,
+            settle_epsilon: 0.001,
+            velocity: RtsCameraVelocity::default(),
+            interpolate_fixed: false,
+            fixed_state: None,
             focus: Transform::IDENTITY,
             target_focus: Transform::IDENTITY,
+            allow_roll: false,
+            auto_rotate_speed: 0.0,
+            auto_rotate_delay: 2.0,
             zoom: 0.0,
             target_zoom: 0.0,
+            zoom_min: 0.0,
+            zoom_max: 1.0,
+            zoom_bookmarks: [None; 10],
             snap: false,
+            frozen: false,
+            ground_height: 0.0,
+            ground_plane: None,
+            ground_plane_transform: GlobalTransform::IDENTITY,
+            follow_ground: true,
+            max_height: f32::INFINITY,
+            constant_height: None,
+            ortho_scale_min: 1.0,
+            ortho_scale_max: 1.0,
+            screen_offset: Vec2::ZERO,
+            view_offset: Vec2::ZERO,
+            lean: Vec2::ZERO,
+            cursor_ground: None,
+            last_pan_delta: Vec3::ZERO,
+            pan_input: Vec2::ZERO,
+            edge_pan: RtsCameraEdgePan::default(),
+            ceiling_zoom_min: 0.0,
         }
     }
 }
 
 impl RtsCamera {
+    /// Stops the camera where it is, e.g. while a pause menu is open. Unlike disabling
+    /// `RtsCameraControls`, this also stops any movement that is in progress.
+    pub fn freeze(&mut self) {
+        self.frozen = true;
+    }
+
+    /// Lets the camera carry on towards its targets after `freeze`.
+    pub fn unfreeze(&mut self) {
+        self.frozen = false;
+    }
+
+    /// Sets both `focus_smoothing` and `zoom_smoothing`, for when they don't need to differ.
+    pub fn set_smoothing(&mut self, smoothing: f32) {
+        self.focus_smoothing = smoothing;
+        self.zoom_smoothing = smoothing;
+    }
+
     /// Sets the camera's position, angle and focus immediately to their current smoothing destination.
     pub fn reset_smoothing(&mut self) {
         self.focus.translation = self.target_focus.translation;
         self.focus.rotation = self.target_focus.rotation;
         self.zoom = self.target_zoom;
         self.angle = self.target_angle;
+        self.velocity = RtsCameraVelocity::default();
+        self.fixed_state = None;
+    }
+
+    /// The position of `target_focus` on the ground plane, where X is world X and Y is world Z.
+    pub fn target_focus_xz(&self) -> Vec2 {
+        self.target_focus.translation.xz()
+    }
+
+    /// Moves `target_focus` to `xz` on the ground plane (where Y is world Z), keeping its height.
+    /// The height is kept on the ground automatically, so this is safer than setting
+    /// `target_focus.translation` directly.
+    pub fn set_target_focus_xz(&mut self, xz: Vec2) {
+        self.target_focus.translation.x = xz.x;
+        self.target_focus.translation.z = xz.y;
+    }
+
+    /// Creates an `RtsCameraTransition` that pans from this camera's targets to `world_point` over
+    /// exactly `duration` seconds, keeping the current zoom and heading. Insert it on the camera
+    /// entity to start panning; a `RtsCameraTransitionFinished` event is sent once it arrives.
+    /// Unlike setting `target_focus`, which arrives whenever smoothing gets there, this has
+    /// predictable timing, e.g. for a "go to event" button.
+    /// # Example
+    /// ```no_run
+    /// # use bevy::prelude::*;
+    /// # use bevy_rts_camera::RtsCamera;
+    /// fn go_to_event(mut commands: Commands, cam_q: Query<(Entity, &RtsCamera)>) {
+    ///     for (entity, cam) in cam_q.iter() {
+    ///         commands
+    ///             .entity(entity)
+    ///             .insert(cam.pan_to(Vec3::new(50.0, 0.0, 20.0), 1.5));
+    ///     }
+    /// }
+    /// ```
+    pub fn pan_to(&self, world_point: Vec3, duration: f32) -> RtsCameraTransition {
+        let mut to = *self;
+        to.set_target_focus_xz(world_point.xz());
+        RtsCameraTransition::new(self, &to, duration)
+    }
+
+    /// Pans the camera this frame like an analog stick, e.g. from an on-screen joystick on mobile.
+    /// `stick` is relative to the camera's heading (where +Y is forward), and its length scales
+    /// the speed from nothing up to `RtsCameraControls::pan_speed` at `1.0`. Longer inputs are
+    /// treated as `1.0`. It's combined with the keyboard and edge pan, so call this every frame
+    /// while the stick is held. Requires `RtsCameraControls`.
+    pub fn set_pan_input(&mut self, stick: Vec2) {
+        self.pan_input = stick.clamp_length_max(1.0);
+    }
+
+    /// Moves `target_focus` by `world_delta`. The camera will still be constrained to `bounds`.
+    /// Useful if you implement your own controls.
+    pub fn pan_by(&mut self, world_delta: Vec3) {
+        debug_assert!(
+            world_delta.is_finite(),
+            "RtsCamera::pan_by called with a non-finite delta: {world_delta}"
+        );
+        self.target_focus.translation += world_delta;
+        self.last_pan_delta += world_delta;
+    }
+
+    /// Pans the view by `pixels` (in logical pixels, where +Y is down), so that the ground that
+    /// was `pixels` away from the focus on screen ends up at the focus. Useful for nudge buttons
+    /// or precise panning in editors. Does nothing if the focus isn't on screen.
+    pub fn pan_screen_pixels(
+        &mut self,
+        pixels: Vec2,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) {
+        let focus = self.focus.translation;
+        let Some(ray) = camera
+            .world_to_viewport(camera_transform, focus)
+            .and_then(|focus_pos| camera.viewport_to_world(camera_transform, focus_pos + pixels))
+        else {
+            return;
+        };
+        let Some(distance) = ray.intersect_plane(focus, InfinitePlane3d::new(Vec3::Y)) else {
+            return;
+        };
+        let delta = ray.get_point(distance) - focus;
+        self.pan_by(Vec3::new(delta.x, 0.0, delta.z));
+    }
+
+    /// The point on the ground at the center of the viewport, e.g. for showing which map cell is
+    /// being viewed. This differs from the focus when `screen_offset` is set or the camera is
+    /// tilted by `max_height`, as it uses the camera's actual transform. The ground is treated as
+    /// flat at the height of the focus. `camera_transform` should be up to date (see
+    /// `world_to_screen`). Returns `None` if the center of the viewport doesn't point at the
+    /// ground.
+    pub fn look_point(&self, camera: &Camera, camera_transform: &GlobalTransform) -> Option<Vec3> {
+        let center = camera.logical_viewport_size()? / 2.0;
+        let ray = camera.viewport_to_world(camera_transform, center)?;
+        let distance =
+            ray.intersect_plane(self.focus.translation, InfinitePlane3d::new(Vec3::Y))?;
+        Some(ray.get_point(distance))
+    }
+
+    /// The approximate size of the visible ground in world units, where `x` is the width across
+    /// the middle of the viewport and `y` is the depth from its bottom edge to its top edge, e.g.
+    /// for sizing a grid overlay or making LOD decisions. Like `look_point`, the ground is treated
+    /// as flat at the height of the focus and `camera_transform` should be up to date. Returns
+    /// `None` if any edge of the viewport doesn't point at the ground, such as when the horizon
+    /// is visible.
+    pub fn visible_extent(
+        &self,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Vec2> {
+        let size = camera.logical_viewport_size()?;
+        let ground = |viewport_pos: Vec2| {
+            let ray = camera.viewport_to_world(camera_transform, viewport_pos)?;
+            let distance =
+                ray.intersect_plane(self.focus.translation, InfinitePlane3d::new(Vec3::Y))?;
+            Some(ray.get_point(distance))
+        };
+        let left = ground(Vec2::new(0.0, size.y / 2.0))?;
+        let right = ground(Vec2::new(size.x, size.y / 2.0))?;
+        let top = ground(Vec2::new(size.x / 2.0, 0.0))?;
+        let bottom = ground(Vec2::new(size.x / 2.0, size.y))?;
+        Some(Vec2::new(left.distance(right), top.distance(bottom)))
+    }
+
+    /// Saves `target_zoom` in `zoom_bookmarks` at `slot` (from `0` to `9`), without saving the
+    /// focus. Does nothing if `slot` is out of range.
+    pub fn save_zoom(&mut self, slot: usize) {
+        if let Some(bookmark) = self.zoom_bookmarks.get_mut(slot) {
+            *bookmark = Some(self.target_zoom);
+        }
+    }
+
+    /// Sets `target_zoom` to the zoom level saved at `slot` by `save_zoom`, leaving the focus
+    /// where it is. The camera will smoothly zoom to it. Returns `false` (and does nothing) if
+    /// nothing was saved there.
+    pub fn recall_zoom(&mut self, slot: usize) -> bool {
+        match self.zoom_bookmarks.get(slot).copied().flatten() {
+            Some(zoom) => {
+                self.target_zoom = zoom.clamp(self.zoom_min, self.zoom_max);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Converts `local`, a direction relative to the camera's heading (where -Z is forward and +X
+    /// is right, like a `Transform`), into the world space direction the built-in controls pan
+    /// in. The Y component is ignored, so the result is always along the ground, even if the
+    /// camera is tilted. Useful for custom controllers, e.g. with isometric input schemes.
+    pub fn pan_direction_for(&self, local: Vec3) -> Vec3 {
+        self.flat_target_focus().rotation * Vec3::new(local.x, 0.0, local.z)
+    }
+
+    /// Changes `target_zoom` by `delta`, clamped between `zoom_min` and `zoom_max`.
+    /// Useful if you implement your own controls.
+    pub fn zoom_by(&mut self, delta: f32) {
+        self.target_zoom = (self.target_zoom + delta).clamp(self.zoom_min, self.zoom_max);
+    }
+
+    /// Rotates `target_focus` around the Y axis by `radians`.
+    /// Useful if you implement your own controls.
+    pub fn rotate_by(&mut self, radians: f32) {
+        self.target_focus.rotate_local_y(radians);
+    }
+
+    /// The current (smoothed) heading of the camera in radians, where `0.0` is looking towards -Z
+    /// and positive values rotate counterclockwise (looking from above).
+    /// Useful for things like compasses or minimap rotation.
+    pub fn yaw(&self) -> f32 {
+        self.focus.rotation.to_euler(EulerRot::YXZ).0
+    }
+
+    /// The height of the ground at `xz` (where Y is world Z) when there are no `Ground` entities
+    /// there, i.e. `ground_height` or the height of `ground_plane`.
+    pub fn ground_height_at(&self, xz: Vec2) -> f32 {
+        let (origin, plane) = self.fallback_ground();
+        let normal = *plane.normal;
+        // Solve `normal.dot(point - origin) == 0.0` for the point's Y
+        origin.y - (normal.x * (xz.x - origin.x) + normal.z * (xz.y - origin.z)) / normal.y
+    }
+
+    /// A point on the ground plane used when there are no `Ground` entities, and its normal.
+    pub(crate) fn fallback_ground(&self) -> (Vec3, InfinitePlane3d) {
+        let up = self.ground_plane_transform.up();
+        match self.ground_plane {
+            Some(_) if up.y.abs() > 0.001 => (
+                self.ground_plane_transform.translation(),
+                InfinitePlane3d::new(up),
+            ),
+            _ => (Vec3::Y * self.ground_height, InfinitePlane3d::new(Vec3::Y)),
+        }
+    }
+
+    /// Captures where the camera is heading for (its targets), e.g. for a save file. Restore it
+    /// later with `restore`.
+    pub fn snapshot(&self) -> RtsCameraSnapshot {
+        RtsCameraSnapshot {
+            focus: self.target_focus,
+            zoom: self.target_zoom,
+            yaw: self.target_focus.rotation.to_euler(EulerRot::YXZ).0,
+            pitch: self.min_angle,
+        }
+    }
+
+    /// Sets the camera's targets to those saved in `snapshot` by `snapshot`. If `animate` is
+    /// `true` the camera smoothly moves there, otherwise it jumps there immediately.
+    pub fn restore(&mut self, snapshot: &RtsCameraSnapshot, animate: bool) {
+        self.target_focus = snapshot.focus;
+        self.target_zoom = snapshot.zoom.clamp(self.zoom_min, self.zoom_max);
+        self.set_yaw(snapshot.yaw);
+        self.set_pitch(snapshot.pitch);
+        if !animate {
+            self.target_angle = self.angle_at_zoom(self.target_zoom);
+            self.reset_smoothing();
+        }
+    }
+
+    /// Sets the target heading of the camera in radians, where `0.0` is looking towards -Z and
+    /// positive values rotate counterclockwise (looking from above). The camera will smoothly
+    /// rotate to the new heading.
+    pub fn set_yaw(&mut self, radians: f32) {
+        self.target_focus.rotation = Quat::from_rotation_y(radians);
+    }
+
+    /// Whether the camera has (almost) finished moving towards its targets, i.e. the focus, zoom
+    /// and angle are all within `epsilon` of their targets.
+    pub fn is_settled(&self, epsilon: f32) -> bool {
+        self.smoothing_remaining() <= epsilon
+    }
+
+    /// The estimated time in seconds until the camera has settled (see `is_settled`), assuming
+    /// the targets don't change.
+    pub fn estimated_settle_time(&self, epsilon: f32) -> f32 {
+        let remaining = self.smoothing_remaining();
+        if remaining <= epsilon {
+            return 0.0;
+        }
+        match self.smoothing {
+            RtsCameraSmoothing::Exponential => {
+                // Whichever is smoother will take longest
+                let smoothness = self.focus_smoothing.max(self.zoom_smoothing);
+                if smoothness <= 0.0 {
+                    0.0
+                } else if smoothness >= 1.0 {
+                    f32::INFINITY
+                } else {
+                    // Each frame the remaining distance is multiplied by `smoothness^(7 * delta)`
+                    (epsilon / remaining).ln() / (7.0 * smoothness.ln())
+                }
+            }
+            RtsCameraSmoothing::Spring { stiffness, damping } => {
+                // The envelope of a damped spring decays at `e^(-rate * t)`
+                let critical = 2.0 * stiffness.sqrt();
+                let rate = if damping < critical {
+                    damping / 2.0
+                } else {
+                    (damping - (damping * damping - critical * critical).sqrt()) / 2.0
+                };
+                // Whichever spring is slower will take longest
+                let rate = rate * self.spring_zoom_speed().min(1.0);
+                if rate <= 0.0 {
+                    f32::INFINITY
+                } else {
+                    (remaining / epsilon).ln() / rate
+                }
+            }
+        }
+    }
+
+    /// The height of the camera above the focus at the given zoom level (ignoring
+    /// `constant_height` and `max_height`).
+    pub fn height_at_zoom(&self, zoom: f32) -> f32 {
+        match self.zoom_curve {
+            ZoomCurve::Exponential if self.height_min > 0.0 => {
+                self.height_max * (self.height_min / self.height_max).powf(zoom)
+            }
+            _ => self.height_max.lerp(self.height_min, zoom),
+        }
+    }
+
+    /// The `scale` of an orthographic projection at the given zoom level (ignoring
+    /// `constant_height`). See `ortho_scale_min`.
+    pub fn ortho_scale_at_zoom(&self, zoom: f32) -> f32 {
+        match self.zoom_curve {
+            ZoomCurve::Exponential if self.ortho_scale_min > 0.0 => {
+                self.ortho_scale_max * (self.ortho_scale_min / self.ortho_scale_max).powf(zoom)
+            }
+            _ => self.ortho_scale_max.lerp(self.ortho_scale_min, zoom),
+        }
+    }
+
+    /// The zoom level at which the camera would be at the given height above the focus. This is
+    /// the inverse of `height_at_zoom`, and is not clamped between `0.0` and `1.0`.
+    pub fn zoom_at_height(&self, height: f32) -> f32 {
+        match self.zoom_curve {
+            ZoomCurve::Exponential if self.height_min > 0.0 => {
+                (height / self.height_max).ln() / (self.height_min / self.height_max).ln()
+            }
+            _ => (self.height_max - height) / (self.height_max - self.height_min),
+        }
+    }
+
+    /// The position of `world_pos` on screen, in logical pixels from the top left of the window,
+    /// or `None` if it's behind the camera (or otherwise outside of the camera's frustum). Useful
+    /// for placing UI like health bars over units. `camera_transform` should be up to date, so
+    /// call this in `PostUpdate` after `TransformSystem::TransformPropagate` (see
+    /// `RtsCameraSystemSet`).
+    pub fn world_to_screen(
+        &self,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+        world_pos: Vec3,
+    ) -> Option<Vec2> {
+        let viewport_pos = camera.world_to_viewport(camera_transform, world_pos)?;
+        // Offset by the viewport's position, for cameras that don't cover the window
+        let viewport_min = camera
+            .logical_viewport_rect()
+            .map_or(Vec2::ZERO, |rect| rect.min);
+        Some(viewport_pos + viewport_min)
+    }
+
+    /// The ray from the camera through `cursor` (in logical pixels from the top left of the
+    /// window, like `Window::cursor_position`), e.g. for raycasting against your own colliders
+    /// rather than just the ground. This is the inverse of `world_to_screen`, so
+    /// `camera_transform` should be up to date in the same way. Returns `None` if the ray can't
+    /// be computed, such as when the camera has no viewport yet.
+    pub fn cursor_ray(
+        &self,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+        cursor: Vec2,
+    ) -> Option<Ray3d> {
+        // Offset by the viewport's position, for cameras that don't cover the window
+        let viewport_min = camera
+            .logical_viewport_rect()
+            .map_or(Vec2::ZERO, |rect| rect.min);
+        camera.viewport_to_world(camera_transform, cursor - viewport_min)
+    }
+
+    /// Sets `target_focus` and `target_zoom` so that `aabb` fits on screen, e.g. for 'zoom to
+    /// selection' commands. `padding` is extra space in world units around each side of the box.
+    /// This takes the camera's heading, angle and aspect ratio into account, but is approximate
+    /// when the camera is tilted. Only the focus is changed for orthographic projections, as
+    /// zoom doesn't change how much they can see.
+    pub fn frame_aabb(&mut self, aabb: Aabb, projection: &Projection, padding: f32) {
+        let center = Vec3::from(aabb.center);
+        self.target_focus.translation.x = center.x;
+        self.target_focus.translation.z = center.z;
+
+        let Projection::Perspective(p) = projection else {
+            return;
+        };
+        // `constant_height` changes the fov to match what the default fov would see at the
+        // zoom's height
+        let fov = if self.constant_height.is_some() {
+            PerspectiveProjection::default().fov
+        } else {
+            p.fov
+        };
+        let half_fov_tan = (fov / 2.0).tan();
+        // Size of the box's footprint along the camera's right and forward directions
+        let flat_focus = self.flat_target_focus();
+        let (right, forward) = (flat_focus.right(), flat_focus.forward());
+        let half_extents = Vec3::from(aabb.half_extents);
+        let half_width = half_extents.x * right.x.abs() + half_extents.z * right.z.abs() + padding;
+        let half_depth =
+            half_extents.x * forward.x.abs() + half_extents.z * forward.z.abs() + padding;
+
+        // The angle depends on the zoom when `dynamic_angle` is enabled, so refine a few times
+        let mut zoom = self.target_zoom;
+        for _ in 0..3 {
+            let angle = self.angle_at_zoom(zoom);
+            let distance = (half_width / (half_fov_tan * p.aspect_ratio))
+                .max(half_depth * angle.cos() / half_fov_tan);
+            zoom = self
+                .zoom_at_height(distance * angle.cos())
+                .clamp(self.zoom_min, self.zoom_max);
+        }
+        self.target_zoom = zoom;
+    }
+
+    /// Like `frame_aabb`, but fits all of `points` on screen, e.g. to keep several moving
+    /// subjects visible during a cinematic. Does nothing if `points` is empty.
+    pub fn frame_points(&mut self, points: &[Vec3], projection: &Projection, padding: f32) {
+        if let Some(aabb) = Aabb::enclosing(points) {
+            self.frame_aabb(aabb, projection, padding);
+        }
+    }
+
+    /// Sets the angle of the camera in radians, where `0.0` is looking directly down. This sets
+    /// `min_angle`, so with `PitchMode::FromZoom` it's the angle when fully zoomed out, and with
+    /// `PitchMode::Manual` it's the angle at any zoom. Has no effect with `PitchMode::Fixed`.
+    /// The camera will smoothly tilt to the new angle.
+    pub fn set_pitch(&mut self, radians: f32) {
+        self.min_angle = radians.clamp(0.0, ANGLE_LIMIT);
+    }
+
+    /// The angle the camera would have at the given zoom level, based on `pitch_mode`,
+    /// `min_angle`, `max_angle`, `dynamic_angle` and `flatten_pitch_while_panning`.
+    fn angle_at_zoom(&self, zoom: f32) -> f32 {
+        let min_angle = self.min_angle.clamp(0.0, ANGLE_LIMIT);
+        let max_angle = self.max_angle.clamp(min_angle, ANGLE_LIMIT);
+        let angle = match self.pitch_mode {
+            PitchMode::FromZoom if self.dynamic_angle => {
+                min_angle.lerp(max_angle, ease_in_circular(zoom.clamp(0.0, 1.0)))
+            }
+            PitchMode::Fixed(angle) => angle.clamp(0.0, ANGLE_LIMIT),
+            _ => min_angle,
+        };
+        // Tilt towards top down while panning
+        angle * (1.0 - self.flatten_pitch_while_panning * self.pan_flatten)
+    }
+
+    /// `target_focus` with only its rotation around the Y axis, so that its directions are along
+    /// the ground.
+    pub(crate) fn flat_target_focus(&self) -> Transform {
+        let (yaw, _, _) = self.target_focus.rotation.to_euler(EulerRot::YXZ);
+        Transform::from_translation(self.target_focus.translation)
+            .with_rotation(Quat::from_rotation_y(yaw))
+    }
+
+    /// Checks for invalid settings, logging a warning and clamping them to a valid value.
+    /// This is called automatically when `RtsCamera` is added.
+    pub fn validate(&mut self) {
+        validate_range(
+            "RtsCamera.focus_smoothing",
+            &mut self.focus_smoothing,
+            0.0,
+            1.0,
+        );
+        validate_range(
+            "RtsCamera.zoom_smoothing",
+            &mut self.zoom_smoothing,
+            0.0,
+            1.0,
+        );
+        if let RtsCameraSmoothing::Spring { stiffness, damping } = &mut self.smoothing {
+            validate_range("RtsCamera.smoothing.stiffness", stiffness, 0.0, f32::MAX);
+            validate_range("RtsCamera.smoothing.damping", damping, 0.0, f32::MAX);
+        }
+        validate_range("RtsCamera.zoom_accel", &mut self.zoom_accel, 0.0, f32::MAX);
+        validate_range(
+            "RtsCamera.settle_epsilon",
+            &mut self.settle_epsilon,
+            0.0,
+            f32::MAX,
+        );
+        validate_range(
+            "RtsCamera.flatten_pitch_while_panning",
+            &mut self.flatten_pitch_while_panning,
+            0.0,
+            1.0,
+        );
+        validate_range("RtsCamera.height_min", &mut self.height_min, 0.0, f32::MAX);
+        validate_range(
+            "RtsCamera.height_max",
+            &mut self.height_max,
+            self.height_min,
+            f32::MAX,
+        );
+        validate_range(
+            "RtsCamera.ortho_scale_min",
+            &mut self.ortho_scale_min,
+            0.0,
+            f32::MAX,
+        );
+        validate_range(
+            "RtsCamera.ortho_scale_max",
+            &mut self.ortho_scale_max,
+            self.ortho_scale_min,
+            f32::MAX,
+        );
+        validate_range("RtsCamera.min_angle", &mut self.min_angle, 0.0, ANGLE_LIMIT);
+        validate_range(
+            "RtsCamera.max_angle",
+            &mut self.max_angle,
+            self.min_angle,
+            ANGLE_LIMIT,
+        );
+        validate_range(
+            "RtsCamera.zoom_max",
+            &mut self.zoom_max,
+            self.zoom_min,
+            f32::INFINITY,
+        );
+        validate_range(
+            "RtsCamera.target_zoom",
+            &mut self.target_zoom,
+            self.zoom_min,
+            self.zoom_max,
+        );
+    }
+
+    /// The largest difference between any of the current values and their targets.
+    fn smoothing_remaining(&self) -> f32 {
+        self.focus
+            .translation
+            .distance(self.target_focus.translation)
+            .max(
+                self.focus
+                    .rotation
+                    .angle_between(self.target_focus.rotation),
+            )
+            .max((self.zoom - self.target_zoom).abs())
+            .max((self.angle - self.target_angle).abs())
+            .max(self.velocity.translation.length())
+            .max(self.velocity.rotation.length())
+            .max(self.velocity.zoom.abs())
+            .max(self.velocity.angle.abs())
+    }
+
+    /// How many times faster the zoom (and angle) spring moves than the focus spring with
+    /// `RtsCameraSmoothing::Spring`, based on `zoom_smoothing` compared to `focus_smoothing`.
+    /// `f32::INFINITY` means the zoom isn't smoothed at all, and `0.0` that it doesn't move.
+    fn spring_zoom_speed(&self) -> f32 {
+        let (focus, zoom) = (self.focus_smoothing, self.zoom_smoothing);
+        if zoom == focus {
+            1.0
+        } else if zoom <= 0.0 {
+            f32::INFINITY
+        } else if zoom >= 1.0 {
+            0.0
+        } else if focus <= 0.0 || focus >= 1.0 {
+            // Nothing to compare to, so the spring is used as it is
+            1.0
+        } else {
+            // Exponential smoothing decays at a rate of `-7.0 * smoothing.ln()`
+            zoom.ln() / focus.ln()
+        }
+    }
+}
+
+/// What an `RtsCamera` does along an axis where its `bounds` are smaller than the visible area.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum SmallBoundsBehavior {
+    /// Keep the focus within the bounds as usual.
+    #[default]
+    Clamp,
+    /// Keep the focus at the center of the bounds, ignoring panning along that axis, so small
+    /// maps stay centered on screen. This is approximate when the camera is tilted.
+    Center,
+}
+
+/// How the zoom level of an `RtsCamera` maps to the camera's height.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum ZoomCurve {
+    /// Each step of zoom changes the height by the same amount.
+    #[default]
+    Linear,
+    /// Each step of zoom changes the height by the same proportion, so zoom is finer when zoomed
+    /// in and coarser when zoomed out. This is how most map apps feel. Requires `height_min` to be
+    /// greater than `0.0`, otherwise `Linear` is used.
+    Exponential,
+}
+
+/// What controls the angle of an `RtsCamera`.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum PitchMode {
+    /// The angle goes from `min_angle` when zoomed out to `max_angle` when zoomed in (or stays at
+    /// `min_angle` if `dynamic_angle` is disabled).
+    #[default]
+    FromZoom,
+    /// The angle stays at this many radians at any zoom, and pitch controls are ignored. Useful
+    /// for a constant top down view that still dollies in and out.
+    Fixed(f32),
+    /// The angle stays at `min_angle` at any zoom, and only changes with pitch controls or
+    /// `RtsCamera::set_pitch`.
+    Manual,
+}
+
+/// How an `RtsCamera` moves from its current values towards its targets.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub enum RtsCameraSmoothing {
+    /// Covers a fixed proportion of the remaining distance each second, based on
+    /// `RtsCamera::focus_smoothing` and `RtsCamera::zoom_smoothing`. Never overshoots.
+    #[default]
+    Exponential,
+    /// Pulls the camera towards its targets with a damped spring, which can overshoot slightly
+    /// before settling. `damping` equal to `2.0 * stiffness.sqrt()` is critically damped (fastest
+    /// without overshooting), and lower values overshoot more. E.g. `stiffness: 100.0` and
+    /// `damping: 15.0` gives a subtle overshoot. Zooming to `zoom_min` or `zoom_max` is always
+    /// at least critically damped, so the zoom eases into the limit instead of overshooting it.
+    Spring {
+        /// How strongly the camera is pulled towards its targets.
+        stiffness: f32,
+        /// How strongly the camera's velocity is resisted.
+        damping: f32,
+    },
+}
+
+/// Which edges of the screen the cursor is edge panning at (see `RtsCamera::edge_pan`). Two edges
+/// can be active at once in the corners.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub struct RtsCameraEdgePan {
+    /// Whether the cursor is at the left edge.
+    pub left: bool,
+    /// Whether the cursor is at the right edge.
+    pub right: bool,
+    /// Whether the cursor is at the top edge.
+    pub up: bool,
+    /// Whether the cursor is at the bottom edge.
+    pub down: bool,
+}
+
+impl RtsCameraEdgePan {
+    /// Whether the cursor is at any edge.
+    pub fn any(&self) -> bool {
+        self.left || self.right || self.up || self.down
+    }
+}
+
+/// The rate of change of an `RtsCamera`'s values, used by `RtsCameraSmoothing::Spring`.
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+pub struct RtsCameraVelocity {
+    /// The velocity of `focus.translation`, in world units per second.
+    pub translation: Vec3,
+    /// The angular velocity of `focus.rotation`, as a scaled axis in radians per second.
+    pub rotation: Vec3,
+    /// The rate of change of `zoom` per second.
+    pub zoom: f32,
+    /// The rate of change of `angle` in radians per second.
+    pub angle: f32,
+}
+
+/// Where an `RtsCamera` was as of the last two fixed timesteps, used by
+/// `RtsCamera::interpolate_fixed`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RtsCameraFixedState {
+    /// `focus`, `zoom` and `angle` as of the fixed timestep before last.
+    pub previous: (Transform, f32, f32),
+    /// `focus`, `zoom` and `angle` as of the last fixed timestep.
+    pub current: (Transform, f32, f32),
+}
+
+/// The shape `target_focus` of an `RtsCamera` is kept within, along the XZ plane (see
+/// `RtsCamera::bounds`).
+#[derive(Copy, Clone, Debug)]
+pub enum RtsCameraBounds {
+    /// An axis-aligned rectangle.
+    Rect(Aabb2d),
+    /// A circle, e.g. for round maps or to keep the camera near a point of interest.
+    Circle(BoundingCircle),
+}
+
+impl RtsCameraBounds {
+    /// Finds the point within the bounds that is closest to `point`.
+    pub fn closest_point(&self, point: Vec2) -> Vec2 {
+        match self {
+            RtsCameraBounds::Rect(aabb) => aabb.closest_point(point),
+            RtsCameraBounds::Circle(circle) => circle.closest_point(point),
+        }
+    }
+
+    /// The largest extent of the bounds, i.e. the longest side of a rectangle or the diameter of
+    /// a circle.
+    pub fn size(&self) -> f32 {
+        match self {
+            RtsCameraBounds::Rect(aabb) => (aabb.max - aabb.min).max_element(),
+            RtsCameraBounds::Circle(circle) => circle.radius() * 2.0,
+        }
+    }
+
+    /// The center of the bounds, and their width and height.
+    fn center_and_extent(&self) -> (Vec2, Vec2) {
+        match self {
+            RtsCameraBounds::Rect(aabb) => ((aabb.min + aabb.max) / 2.0, aabb.max - aabb.min),
+            RtsCameraBounds::Circle(circle) => (circle.center, Vec2::splat(circle.radius() * 2.0)),
+        }
+    }
+}
+
+impl From<Aabb2d> for RtsCameraBounds {
+    fn from(aabb: Aabb2d) -> Self {
+        RtsCameraBounds::Rect(aabb)
+    }
+}
+
+impl From<BoundingCircle> for RtsCameraBounds {
+    fn from(circle: BoundingCircle) -> Self {
+        RtsCameraBounds::Circle(circle)
     }
 }
 
@@ -191,14 +1234,57 @@ impl RtsCamera {
 #[reflect(Component)]
 pub struct Ground;
 
+/// Marks an entity that the RTS camera shouldn't zoom out through, e.g. the roof of an indoor
+/// area. When one of these meshes is directly above the focus (using a ray cast), zooming out
+/// stops just below it. See `RtsCamera::ceiling_zoom_min`.
+/// Unlike `RtsCamera::max_height`, which tilts the camera to stay below a fixed height, this limits
+/// the zoom, so the view doesn't change angle.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+#[reflect(Component)]
+pub struct Ceiling;
+
+/// The position of an `RtsCamera`, e.g. for saving and loading it. See `RtsCamera::snapshot` and
+/// `RtsCamera::restore`. With the `serde` feature, this can be serialized.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RtsCameraSnapshot {
+    /// The focus, like `RtsCamera::target_focus`. Its rotation is replaced by `yaw` when restoring.
+    pub focus: Transform,
+    /// The zoom level.
+    pub zoom: f32,
+    /// The heading in radians, like `RtsCamera::yaw`.
+    pub yaw: f32,
+    /// The angle in radians, like `RtsCamera::set_pitch`.
+    pub pitch: f32,
+}
+
+/// A point where a ray hit an entity marked with `Ground`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct GroundHit {
+    /// The ground entity that was hit.
+    pub entity: Entity,
+    /// The world position of the hit.
+    pub position: Vec3,
+}
+
 fn initialize(mut cam_q: Query<&mut RtsCamera, Added<RtsCamera>>) {
     for mut cam in cam_q.iter_mut() {
+        cam.validate();
         // Snap to targets when RtsCamera is added. Note that we snap whole transform, not just XZ
         // translation like snap_to system.
         cam.zoom = cam.target_zoom;
         cam.focus = cam.target_focus;
-        cam.angle = cam.min_angle;
-        cam.target_angle = cam.min_angle;
+        cam.angle = cam.angle_at_zoom(cam.zoom);
+        cam.target_angle = cam.angle;
+    }
+}
+
+fn reset_pan_delta(mut cam_q: Query<&mut RtsCamera>) {
+    for mut cam in cam_q.iter_mut() {
+        // Avoid triggering change detection every frame
+        if cam.last_pan_delta != Vec3::ZERO {
+            cam.last_pan_delta = Vec3::ZERO;
+        }
     }
 }
 
@@ -207,16 +1293,181 @@ fn follow_ground(
     ground_q: Query<Entity, With<Ground>>,
     mut raycast: Raycast,
 ) {
-    for mut cam in cam_q.iter_mut() {
+    for mut cam in cam_q.iter_mut().filter(|cam| cam.follow_ground) {
         let ray_start = Vec3::new(
             cam.target_focus.translation.x,
             cam.target_focus.translation.y + cam.height_max,
             cam.target_focus.translation.z,
         );
-        if let Some(hit1) = cast_ray(&mut raycast, ray_start, Dir3::NEG_Y, &|entity| {
+        let height = match cast_ray(&mut raycast, ray_start, Dir3::NEG_Y, &|entity| {
             ground_q.get(entity).is_ok()
         }) {
-            cam.target_focus.translation.y = hit1.position().y;
+            Some((_, hit)) => hit.position().y,
+            None => cam.ground_height_at(cam.target_focus_xz()),
+        };
+        // Avoid triggering change detection every frame
+        if cam.target_focus.translation.y != height {
+            cam.target_focus.translation.y = height;
+        }
+    }
+}
+
+fn update_ground_plane(mut cam_q: Query<&mut RtsCamera>, plane_q: Query<&GlobalTransform>) {
+    for mut cam in cam_q.iter_mut() {
+        let Some(transform) = cam.ground_plane.and_then(|entity| plane_q.get(entity).ok()) else {
+            continue;
+        };
+        // Avoid triggering change detection every frame
+        if cam.ground_plane_transform != *transform {
+            cam.ground_plane_transform = *transform;
+        }
+    }
+}
+
+fn limit_zoom_to_ceiling(
+    mut cam_q: Query<&mut RtsCamera>,
+    ceiling_q: Query<Entity, With<Ceiling>>,
+    mut raycast: Raycast,
+) {
+    // Without any ceilings there's nothing to raycast against
+    if ceiling_q.is_empty() {
+        for mut cam in cam_q.iter_mut() {
+            // Avoid triggering change detection every frame
+            if cam.ceiling_zoom_min != cam.zoom_min {
+                cam.ceiling_zoom_min = cam.zoom_min;
+            }
+        }
+        return;
+    }
+    for mut cam in cam_q.iter_mut() {
+        let focus = cam.target_focus.translation;
+        let min_zoom = match cast_ray(&mut raycast, focus, Dir3::Y, &|entity| {
+            ceiling_q.get(entity).is_ok()
+        }) {
+            Some((_, hit)) => {
+                let max_height = (hit.position().y - focus.y - CEILING_CLEARANCE).max(0.0);
+                cam.zoom_at_height(max_height)
+                    .clamp(cam.zoom_min, cam.zoom_max)
+            }
+            None => cam.zoom_min,
+        };
+        // Avoid triggering change detection every frame
+        if cam.ceiling_zoom_min != min_zoom {
+            cam.ceiling_zoom_min = min_zoom;
+        }
+        if cam.target_zoom < min_zoom {
+            cam.target_zoom = min_zoom;
+        }
+    }
+}
+
+fn update_cursor_ground(
+    mut cam_q: Query<&mut RtsCamera>,
+    ground_q: Query<Entity, With<Ground>>,
+    cursor_ray: Res<CursorRay>,
+    mut raycast: Raycast,
+) {
+    let cursor_ground = (**cursor_ray).and_then(|ray| {
+        cast_ray(&mut raycast, ray.origin, ray.direction, &|entity| {
+            ground_q.get(entity).is_ok()
+        })
+        .map(|(entity, hit)| GroundHit {
+            entity: *entity,
+            position: hit.position(),
+        })
+    });
+    for mut cam in cam_q.iter_mut() {
+        // Avoid triggering change detection every frame
+        if cam.cursor_ground != cursor_ground {
+            cam.cursor_ground = cursor_ground;
+        }
+    }
+}
+
+fn level_target_focus(mut cam_q: Query<&mut RtsCamera>) {
+    for mut cam in cam_q.iter_mut().filter(|cam| !cam.allow_roll) {
+        let level = cam.flat_target_focus().rotation;
+        // Avoid triggering change detection every frame
+        if cam.target_focus.rotation != level {
+            cam.target_focus.rotation = level;
+        }
+    }
+}
+
+fn auto_rotate(
+    mut cam_q: Query<(Entity, &mut RtsCamera)>,
+    time: Res<Time<Real>>,
+    // The rotation each camera was left at last frame, and how long it's been idle for
+    mut last: Local<EntityHashMap<(Quat, f32)>>,
+) {
+    // Forget cameras that have been despawned
+    last.retain(|entity, _| cam_q.contains(*entity));
+    for (entity, mut cam) in cam_q.iter_mut() {
+        if cam.auto_rotate_speed == 0.0 {
+            last.remove(&entity);
+            continue;
+        }
+        let (last_rotation, idle_time) = last
+            .entry(entity)
+            .or_insert((cam.target_focus.rotation, f32::INFINITY));
+        // Something else rotated the camera since last frame
+        if last_rotation.angle_between(cam.target_focus.rotation) > 0.0001 {
+            *idle_time = 0.0;
+        }
+        *idle_time += time.delta_seconds();
+        if *idle_time >= cam.auto_rotate_delay {
+            let radians = cam.auto_rotate_speed * time.delta_seconds();
+            cam.rotate_by(radians);
+        }
+        *last_rotation = cam.target_focus.rotation;
+    }
+}
+
+fn stable_framing(
+    mut cam_q: Query<(Entity, &mut RtsCamera, &Projection)>,
+    // The aspect ratio of each camera last frame
+    mut last_aspect: Local<EntityHashMap<f32>>,
+) {
+    // Forget cameras that have been despawned
+    last_aspect.retain(|entity, _| cam_q.contains(*entity));
+    for (entity, mut cam, projection) in cam_q.iter_mut() {
+        let Projection::Perspective(ref p) = *projection else {
+            continue;
+        };
+        let Some(old_aspect) = last_aspect.insert(entity, p.aspect_ratio) else {
+            continue;
+        };
+        if !cam.stable_framing_on_resize || old_aspect == p.aspect_ratio || p.aspect_ratio <= 0.0 {
+            continue;
+        }
+        // The visible width is proportional to both the height and the aspect ratio
+        let scale = old_aspect / p.aspect_ratio;
+        let (min, max) = (cam.zoom_min, cam.zoom_max);
+        cam.target_zoom = cam
+            .zoom_at_height(cam.height_at_zoom(cam.target_zoom) * scale)
+            .clamp(min, max);
+        // Jump straight there, as the view has already changed this frame
+        cam.zoom = cam
+            .zoom_at_height(cam.height_at_zoom(cam.zoom) * scale)
+            .clamp(min, max);
+    }
+}
+
+fn limit_zoom_to_bounds(mut cam_q: Query<(&mut RtsCamera, &Projection)>) {
+    for (mut cam, projection) in cam_q.iter_mut().filter(|(cam, _)| cam.limit_zoom_to_bounds) {
+        let Projection::Perspective(ref p) = *projection else {
+            continue;
+        };
+        let bounds_size = cam.bounds.size();
+        // Height at which the visible width of the ground matches the size of the bounds
+        let max_height =
+            bounds_size * cam.angle.cos() / (2.0 * (p.fov / 2.0).tan() * p.aspect_ratio);
+        let min_zoom = cam
+            .zoom_at_height(max_height)
+            .clamp(cam.zoom_min, cam.zoom_max);
+        // Avoid triggering change detection every frame
+        if cam.target_zoom < min_zoom {
+            cam.target_zoom = min_zoom;
         }
     }
 }
@@ -224,77 +1475,444 @@ fn follow_ground(
 fn snap_to_target(mut cam_q: Query<&mut RtsCamera>) {
     // When snapping in a top down camera, only the XZ should be snapped. The Y coord is controlled
     // by zoom and that should remain smoothed, as should rotation.
-    for mut cam in cam_q.iter_mut() {
+    for mut cam in cam_q.iter_mut().filter(|cam| !cam.frozen) {
         if cam.snap {
             cam.focus.translation.x = cam.target_focus.translation.x;
             cam.focus.translation.z = cam.target_focus.translation.z;
+            cam.velocity.translation.x = 0.0;
+            cam.velocity.translation.z = 0.0;
             cam.snap = false;
         }
     }
 }
 
+fn flatten_pitch(mut cam_q: Query<&mut RtsCamera>, time: Res<Time<Real>>) {
+    for mut cam in cam_q.iter_mut() {
+        let target = if cam.last_pan_delta != Vec3::ZERO && cam.flatten_pitch_while_panning > 0.0 {
+            1.0
+        } else {
+            0.0
+        };
+        // Avoid triggering change detection every frame
+        if cam.pan_flatten != target {
+            let step = time.delta_seconds() / FLATTEN_PITCH_TIME;
+            cam.pan_flatten += (target - cam.pan_flatten).clamp(-step, step);
+        }
+    }
+}
+
 fn dynamic_angle(mut query: Query<&mut RtsCamera>) {
-    for mut cam in query.iter_mut().filter(|cam| cam.dynamic_angle) {
-        cam.target_angle = cam
-            .min_angle
-            .lerp(MAX_ANGLE, ease_in_circular(cam.target_zoom));
+    for mut cam in query.iter_mut() {
+        let target_angle = cam.angle_at_zoom(cam.target_zoom);
+        // Avoid triggering change detection every frame
+        if cam.target_angle != target_angle {
+            cam.target_angle = target_angle;
+        }
     }
 }
 
 fn move_towards_target(mut cam_q: Query<&mut RtsCamera>, time: Res<Time<Real>>) {
-    for mut cam in cam_q.iter_mut() {
-        cam.focus.translation = cam.focus.translation.lerp(
-            cam.target_focus.translation,
-            1.0 - cam.smoothness.powi(7).powf(time.delta_seconds()),
-        );
-        cam.focus.rotation = cam.focus.rotation.lerp(
-            cam.target_focus.rotation,
-            1.0 - cam.smoothness.powi(7).powf(time.delta_seconds()),
-        );
-        cam.zoom = cam.zoom.lerp(
-            cam.target_zoom,
-            1.0 - cam.smoothness.powi(7).powf(time.delta_seconds()),
-        );
-        cam.angle = cam.angle.lerp(
-            cam.target_angle,
-            1.0 - cam.smoothness.powi(7).powf(time.delta_seconds()),
-        );
+    // Cameras using `interpolate_fixed` are moved in `fixed_move_towards_target` instead
+    for mut cam in cam_q
+        .iter_mut()
+        .filter(|cam| !cam.frozen && !cam.interpolate_fixed)
+    {
+        let mut next = *cam;
+        smooth_towards_target(&mut next, time.delta_seconds());
+        // Avoid triggering change detection every frame once the camera has settled
+        if (next.focus, next.zoom, next.angle, next.velocity)
+            != (cam.focus, cam.zoom, cam.angle, cam.velocity)
+        {
+            cam.focus = next.focus;
+            cam.zoom = next.zoom;
+            cam.angle = next.angle;
+            cam.velocity = next.velocity;
+        }
     }
 }
 
-fn apply_bounds(mut cam_q: Query<&mut RtsCamera>) {
-    for mut cam in cam_q.iter_mut() {
-        let closest_point = cam.bounds.closest_point(Vec2::new(
+/// Moves `cam`'s focus, zoom and angle towards their targets over `dt` seconds.
+fn smooth_towards_target(cam: &mut RtsCamera, dt: f32) {
+    let threshold = cam.smoothing_snap_threshold;
+    if cam.focus.translation.distance(cam.target_focus.translation) < threshold {
+        cam.focus.translation = cam.target_focus.translation;
+        cam.velocity.translation = Vec3::ZERO;
+    }
+    if (cam.height_at_zoom(cam.zoom) - cam.height_at_zoom(cam.target_zoom)).abs() < threshold {
+        cam.zoom = cam.target_zoom;
+        cam.velocity.zoom = 0.0;
+    }
+    if let RtsCameraSmoothing::Spring { stiffness, damping } = cam.smoothing {
+        spring_towards_target(cam, stiffness, damping, dt);
+    } else {
+        // Clamped so the camera never moves past its targets, even if the smoothing has been
+        // set outside of `0.0..=1.0` since it was validated
+        let lerp_factor = |smoothness: f32| (1.0 - smoothness.powi(7).powf(dt)).clamp(0.0, 1.0);
+        let t = lerp_factor(cam.focus_smoothing);
+        let zoom_t = lerp_factor(cam.zoom_smoothing);
+        cam.focus.translation = cam.focus.translation.lerp(cam.target_focus.translation, t);
+        cam.focus.rotation = cam.focus.rotation.lerp(cam.target_focus.rotation, t);
+        let zoom = cam.zoom.lerp(cam.target_zoom, zoom_t);
+        if cam.zoom_accel > 0.0 && dt > 0.0 {
+            let remaining = cam.target_zoom - cam.zoom;
+            // Never faster than the smoothing, or than can be stopped before the target
+            let max_speed = (2.0 * cam.zoom_accel * remaining.abs()).sqrt();
+            let desired = ((zoom - cam.zoom) / dt).clamp(-max_speed, max_speed);
+            let max_change = cam.zoom_accel * dt;
+            cam.velocity.zoom += (desired - cam.velocity.zoom).clamp(-max_change, max_change);
+            let step = cam.velocity.zoom * dt;
+            if step * remaining >= remaining * remaining {
+                // Arrived, so stop rather than coasting past the target
+                cam.zoom = cam.target_zoom;
+                cam.velocity.zoom = 0.0;
+            } else {
+                cam.zoom += step;
+            }
+        } else {
+            cam.zoom = zoom;
+        }
+        cam.angle = cam.angle.lerp(cam.target_angle, zoom_t);
+    }
+    if cam.dynamic_angle && cam.pitch_mode == PitchMode::FromZoom {
+        // Derive the angle from the smoothed zoom, rather than smoothing it separately, so
+        // the camera tilts in lockstep with its height instead of leading or lagging it
+        cam.angle = cam.angle_at_zoom(cam.zoom);
+        cam.velocity.angle = 0.0;
+    }
+}
+
+/// The longest step used when integrating spring smoothing. Longer frames are split into several
+/// steps, which keeps the spring stable at low frame rates.
+const SPRING_STEP: f32 = 1.0 / 120.0;
+
+fn spring_towards_target(cam: &mut RtsCamera, stiffness: f32, damping: f32, delta: f32) {
+    // Scaling the stiffness by the square of the speed and the damping by the speed runs the zoom
+    // spring faster or slower without changing how much it overshoots
+    let zoom_speed = cam.spring_zoom_speed();
+    if zoom_speed.is_infinite() {
+        cam.zoom = cam.target_zoom;
+        cam.angle = cam.target_angle;
+        cam.velocity.zoom = 0.0;
+        cam.velocity.angle = 0.0;
+    } else if zoom_speed <= 0.0 {
+        cam.velocity.zoom = 0.0;
+        cam.velocity.angle = 0.0;
+    }
+    let zoom_stiffness = stiffness * zoom_speed * zoom_speed;
+    let angle_damping = damping * zoom_speed;
+    // Overshooting the zoom limits would show zoom levels that can't otherwise be reached
+    let zoom_damping = if cam.target_zoom <= cam.zoom_min || cam.target_zoom >= cam.zoom_max {
+        angle_damping.max(2.0 * zoom_stiffness.sqrt())
+    } else {
+        angle_damping
+    };
+    let move_zoom = zoom_speed.is_finite() && zoom_speed > 0.0;
+    // A faster zoom spring needs shorter steps to stay stable
+    let step = SPRING_STEP / if move_zoom { zoom_speed.max(1.0) } else { 1.0 };
+    let steps = (delta / step).ceil().max(1.0);
+    let h = delta / steps;
+    for _ in 0..steps as u32 {
+        // Semi-implicit Euler: update the velocity first, then move using the new velocity
+        let accel = (cam.target_focus.translation - cam.focus.translation) * stiffness
+            - cam.velocity.translation * damping;
+        cam.velocity.translation += accel * h;
+        cam.focus.translation += cam.velocity.translation * h;
+
+        let mut rotation_diff = cam.target_focus.rotation * cam.focus.rotation.inverse();
+        // Take the shortest way around
+        if rotation_diff.w < 0.0 {
+            rotation_diff = -rotation_diff;
+        }
+        let accel = rotation_diff.to_scaled_axis() * stiffness - cam.velocity.rotation * damping;
+        cam.velocity.rotation += accel * h;
+        cam.focus.rotation =
+            (Quat::from_scaled_axis(cam.velocity.rotation * h) * cam.focus.rotation).normalize();
+
+        if move_zoom {
+            let accel =
+                (cam.target_zoom - cam.zoom) * zoom_stiffness - cam.velocity.zoom * zoom_damping;
+            cam.velocity.zoom += accel * h;
+            cam.zoom += cam.velocity.zoom * h;
+
+            let accel = (cam.target_angle - cam.angle) * zoom_stiffness
+                - cam.velocity.angle * angle_damping;
+            cam.velocity.angle += accel * h;
+            cam.angle += cam.velocity.angle * h;
+        }
+    }
+}
+
+fn apply_bounds(
+    mut cam_q: Query<(Entity, &mut RtsCamera, Option<&Projection>)>,
+    // Where the focus of each camera was last navigable
+    mut last_navigable: Local<EntityHashMap<Vec2>>,
+) {
+    // Forget cameras that have been despawned
+    last_navigable.retain(|entity, _| cam_q.contains(*entity));
+    for (entity, mut cam, projection) in cam_q.iter_mut() {
+        let mut point = Vec2::new(
             cam.target_focus.translation.x,
             -cam.target_focus.translation.z,
-        ));
+        );
+        if cam.small_bounds_behavior == SmallBoundsBehavior::Center {
+            let (center, extent) = cam.bounds.center_and_extent();
+            let visible = visible_ground_extent(&cam, projection);
+            if visible.x >= extent.x {
+                point.x = center.x;
+            }
+            if visible.y >= extent.y {
+                point.y = center.y;
+            }
+        }
+        let closest_point = cam.bounds.closest_point(point);
         let closest_point = Vec3::new(
             closest_point.x,
             cam.target_focus.translation.y,
             -closest_point.y,
         );
-        cam.target_focus.translation = closest_point;
+        // Avoid triggering change detection every frame
+        if cam.target_focus.translation != closest_point {
+            cam.target_focus.translation = closest_point;
+        }
+
+        let Some(navigable) = cam.navigable else {
+            last_navigable.remove(&entity);
+            continue;
+        };
+        let target = cam.target_focus_xz();
+        if navigable(target) {
+            last_navigable.insert(entity, target);
+            continue;
+        }
+        let Some(&last) = last_navigable.get(&entity) else {
+            // Never been anywhere navigable, so there's nowhere to go back to
+            continue;
+        };
+        // Slide along the edge by only moving along one axis
+        let slide_x = Vec2::new(target.x, last.y);
+        let slide_z = Vec2::new(last.x, target.y);
+        let allowed = if navigable(slide_x) {
+            slide_x
+        } else if navigable(slide_z) {
+            slide_z
+        } else {
+            // Binary search for the edge between the last navigable point and the target
+            let (mut valid, mut invalid) = (0.0, 1.0);
+            for _ in 0..16 {
+                let mid = (valid + invalid) / 2.0;
+                if navigable(last.lerp(target, mid)) {
+                    valid = mid;
+                } else {
+                    invalid = mid;
+                }
+            }
+            last.lerp(target, valid)
+        };
+        cam.set_target_focus_xz(allowed);
+        last_navigable.insert(entity, allowed);
     }
 }
 
-fn update_camera_transform(mut cam_q: Query<(&mut Transform, &RtsCamera)>) {
-    for (mut tfm, cam) in cam_q.iter_mut() {
-        let rotation = Quat::from_rotation_x(cam.angle - 90f32.to_radians());
-        let camera_height = cam.height_max.lerp(cam.height_min, cam.zoom);
-        let camera_offset = camera_height * cam.angle.tan();
+/// The approximate size of the ground visible at `target_zoom`, along world X and -Z (like
+/// `RtsCameraBounds`).
+fn visible_ground_extent(cam: &RtsCamera, projection: Option<&Projection>) -> Vec2 {
+    let angle = cam.angle_at_zoom(cam.target_zoom);
+    // Across and along the view, at the focus' distance from the camera
+    let view_size = match projection {
+        Some(Projection::Orthographic(p)) => p.area.size(),
+        Some(Projection::Perspective(p)) => {
+            // `constant_height` changes the fov to match what the default fov would see at the
+            // zoom's height
+            let fov = match cam.constant_height {
+                Some(_) => PerspectiveProjection::default().fov,
+                None => p.fov,
+            };
+            let distance = cam.height_at_zoom(cam.target_zoom) / angle.cos();
+            let height = 2.0 * distance * (fov / 2.0).tan();
+            Vec2::new(height * p.aspect_ratio, height)
+        }
+        None => return Vec2::ZERO,
+    };
+    // The view is stretched along the ground the more the camera is tilted
+    let size = Vec2::new(view_size.x, view_size.y / angle.cos().max(0.1));
+    // The size of the box around the view, as it's rotated by the camera's heading
+    let (sin, cos) = cam
+        .target_focus
+        .rotation
+        .to_euler(EulerRot::YXZ)
+        .0
+        .sin_cos();
+    Vec2::new(
+        (size.x * cos).abs() + (size.y * sin).abs(),
+        (size.x * sin).abs() + (size.y * cos).abs(),
+    )
+}
 
-        tfm.rotation = cam.focus.rotation * rotation;
+fn fixed_move_towards_target(mut cam_q: Query<&mut RtsCamera>, time: Res<Time<Fixed>>) {
+    for mut cam in cam_q.iter_mut() {
+        if !cam.interpolate_fixed {
+            // Avoid triggering change detection every frame
+            if cam.fixed_state.is_some() {
+                cam.fixed_state = None;
+            }
+            continue;
+        }
+        let mut next = *cam;
+        if !next.frozen {
+            smooth_towards_target(&mut next, time.delta_seconds());
+        }
+        next.fixed_state = Some(RtsCameraFixedState {
+            previous: (cam.focus, cam.zoom, cam.angle),
+            current: (next.focus, next.zoom, next.angle),
+        });
+        // Avoid triggering change detection every frame once the camera has settled
+        if (next.fixed_state, next.velocity) != (cam.fixed_state, cam.velocity) {
+            *cam = next;
+        }
+    }
+}
+
+/// The `focus`, `zoom` and `angle` to draw `cam` at this frame, which are interpolated between the
+/// last two fixed timesteps when using `interpolate_fixed`.
+fn rendered_state(cam: &RtsCamera, fixed_time: &Time<Fixed>) -> (Transform, f32, f32) {
+    match cam.fixed_state {
+        // Unless the camera has been moved since (e.g. by `reset_smoothing`), in which case it's
+        // drawn where it is now
+        Some(RtsCameraFixedState { previous, current })
+            if cam.interpolate_fixed && current == (cam.focus, cam.zoom, cam.angle) =>
+        {
+            let t = fixed_time.overstep_fraction();
+            let focus = Transform {
+                translation: previous.0.translation.lerp(current.0.translation, t),
+                rotation: previous.0.rotation.slerp(current.0.rotation, t),
+                scale: current.0.scale,
+            };
+            (
+                focus,
+                previous.1.lerp(current.1, t),
+                previous.2.lerp(current.2, t),
+            )
+        }
+        _ => (cam.focus, cam.zoom, cam.angle),
+    }
+}
+
+fn update_camera_transform(
+    mut cam_q: Query<(&mut Transform, &RtsCamera, Option<&Projection>)>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    for (mut tfm, cam, projection) in cam_q.iter_mut() {
+        let (mut focus, zoom, angle) = rendered_state(cam, &fixed_time);
+        focus.translation += Vec3::new(cam.lean.x, 0.0, cam.lean.y);
+        let mut angle = angle.clamp(0.0, ANGLE_LIMIT);
+        let mut camera_height = cam.constant_height.unwrap_or(cam.height_at_zoom(zoom));
+        let camera_offset = camera_height * angle.tan();
+        // Lower the camera while keeping its horizontal distance from the focus, and tilt it so it
+        // keeps looking at the focus
+        let allowed_height = (cam.max_height - focus.translation.y).max(0.0);
+        if camera_height > allowed_height {
+            camera_height = allowed_height;
+            angle = camera_offset.atan2(camera_height).min(ANGLE_LIMIT);
+        }
+        let rotation = Quat::from_rotation_x(angle - 90f32.to_radians());
+
+        tfm.rotation = focus.rotation * rotation;
         tfm.translation =
-            cam.focus.translation + (Vec3::Y * camera_height) + (cam.focus.back() * camera_offset);
+            focus.translation + (Vec3::Y * camera_height) + (focus.back() * camera_offset);
+
+        if cam.screen_offset != Vec2::ZERO {
+            // Size of the visible area at the focus' distance from the camera
+            let view_size = match projection {
+                Some(Projection::Perspective(p)) => {
+                    let distance = tfm.translation.distance(focus.translation);
+                    let height = 2.0 * distance * (p.fov / 2.0).tan();
+                    Vec2::new(height * p.aspect_ratio, height)
+                }
+                Some(Projection::Orthographic(p)) => p.area.size(),
+                None => Vec2::ZERO,
+            };
+            let offset = cam.screen_offset * view_size;
+            // Moving the camera the opposite way moves the focus the desired way on screen
+            let shift = tfm.right() * offset.x + tfm.up() * offset.y;
+            tfm.translation -= shift;
+        }
+
+        if cam.view_offset != Vec2::ZERO {
+            let shift = tfm.right() * cam.view_offset.x + tfm.up() * cam.view_offset.y;
+            tfm.translation += shift;
+        }
+    }
+}
+
+fn update_projection(
+    mut cam_q: Query<(&RtsCamera, &mut Projection)>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    for (cam, mut projection) in cam_q.iter_mut() {
+        let (_, zoom, _) = rendered_state(cam, &fixed_time);
+        let Some(constant_height) = cam.constant_height else {
+            if let Projection::Orthographic(ref mut p) = *projection {
+                p.scale = cam.ortho_scale_at_zoom(zoom);
+            }
+            continue;
+        };
+        // How much bigger the visible area would be if the camera was moved instead
+        let ratio = cam.height_at_zoom(zoom) / constant_height;
+        match *projection {
+            Projection::Perspective(ref mut p) => {
+                let default_fov = PerspectiveProjection::default().fov;
+                p.fov = 2.0 * (ratio * (default_fov / 2.0).tan()).atan();
+            }
+            Projection::Orthographic(ref mut p) => {
+                p.scale = ratio;
+            }
+        }
     }
 }
 
+fn detect_settled(
+    cam_q: Query<(Entity, &RtsCamera, Has<RtsCameraTransition>)>,
+    mut settled: EventWriter<RtsCameraSettled>,
+    // Cameras that were moving as of the last frame
+    mut moving: Local<EntityHashSet>,
+) {
+    // Forget cameras that have been despawned
+    moving.retain(|entity| cam_q.contains(*entity));
+    for (entity, cam, transitioning) in cam_q.iter() {
+        if transitioning || !cam.is_settled(cam.settle_epsilon) {
+            moving.insert(entity);
+        } else if moving.remove(&entity) {
+            settled.send(RtsCameraSettled { entity });
+        }
+    }
+}
+
+/// Finds where `ray` hits the ground, which is either the nearest entity marked with `Ground`, or
+/// if there are none, the ground plane of `cam` (see `RtsCamera::ground_plane`).
+pub(crate) fn ray_to_ground(
+    raycast: &mut Raycast,
+    ground_q: &Query<Entity, With<Ground>>,
+    ray: Ray3d,
+    cam: &RtsCamera,
+) -> Option<Vec3> {
+    cast_ray(raycast, ray.origin, ray.direction, &|entity| {
+        ground_q.get(entity).is_ok()
+    })
+    .map(|(_, hit)| hit.position())
+    .or_else(|| {
+        // Fall back to the ground plane
+        let (origin, plane) = cam.fallback_ground();
+        ray.intersect_plane(origin, plane)
+            .map(|distance| ray.get_point(distance))
+    })
+}
+
 fn cast_ray<'a>(
     raycast: &'a mut Raycast<'_, '_>,
     origin: Vec3,
     dir: Dir3,
     filter: &'a dyn Fn(Entity) -> bool,
-) -> Option<&'a IntersectionData> {
+) -> Option<&'a (Entity, IntersectionData)> {
     let ray1 = Ray3d::new(origin, Vec3::from(dir));
     let hits1 = raycast.cast_ray(
         ray1,
@@ -303,7 +1921,24 @@ fn cast_ray<'a>(
             ..default()
         },
     );
-    hits1.first().map(|(_, hit)| hit)
+    // Hits are sorted by distance, so the first is the nearest ground
+    hits1.first()
+}
+
+/// Logs a warning and clamps `value` if it is outside `min..=max`. NaN is replaced with `0.0`
+/// (clamped to the range).
+pub(crate) fn validate_range(name: &str, value: &mut f32, min: f32, max: f32) {
+    if !(min..=max).contains(value) {
+        let clamped = if value.is_nan() {
+            0.0f32.clamp(min, max)
+        } else {
+            value.clamp(min, max)
+        };
+        warn!(
+            "{name} should be between {min} and {max}, but was {value}. Using {clamped} instead."
+        );
+        *value = clamped;
+    }
 }
 
 fn ease_in_circular(x: f32) -> f32 {
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+
+use crate::RtsCamera;
+
+/// Add this to an `RtsCamera` to keep it looking at the same place as another `RtsCamera`, e.g.
+/// for a top-down minimap camera. Each frame the XZ of the other camera's `target_focus` is copied
+/// into this camera's, while this camera keeps its own zoom, angle and heading. Any
+/// `RtsCameraControls` driving this camera are ignored.
+/// The other camera can't be a mirror itself.
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_rts_camera::{RtsCamera, RtsCameraControls, RtsCameraMirror};
+/// fn setup(mut commands: Commands) {
+///     let main = commands
+///         .spawn((
+///             Camera3dBundle::default(),
+///             RtsCamera::default(),
+///             RtsCameraControls::default(),
+///         ))
+///         .id();
+///     commands.spawn((
+///         Camera3dBundle {
+///             camera: Camera {
+///                 order: 1,
+///                 ..default()
+///             },
+///             ..default()
+///         },
+///         RtsCamera {
+///             min_angle: 0.0,
+///             dynamic_angle: false,
+///             ..default()
+///         },
+///         RtsCameraMirror(main),
+///     ));
+/// }
+/// ```
+#[derive(Component, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RtsCameraMirror(pub Entity);
+
+pub(crate) fn mirror_focus(
+    mut mirror_q: Query<(&mut RtsCamera, &RtsCameraMirror)>,
+    source_q: Query<&RtsCamera, Without<RtsCameraMirror>>,
+) {
+    for (mut cam, mirror) in mirror_q.iter_mut() {
+        let Ok(source) = source_q.get(mirror.0) else {
+            continue;
+        };
+        cam.set_target_focus_xz(source.target_focus_xz());
+    }
+}
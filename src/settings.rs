@@ -0,0 +1,41 @@
+use bevy::reflect::serde::{TypedReflectDeserializer, TypedReflectSerializer};
+use bevy::reflect::{FromReflect, GetTypeRegistration, TypeRegistry};
+use serde::de::DeserializeSeed;
+
+use crate::RtsCameraControls;
+
+/// Saves `controls` as a RON string, e.g. for a settings menu. Load it again with
+/// `load_controls`. Note that `RtsCameraControls.camera` is not saved.
+/// # Example
+/// ```
+/// # use bevy_rts_camera::{load_controls, save_controls, RtsCameraControls};
+/// let controls = RtsCameraControls {
+///     pan_speed: 5.0,
+///     ..Default::default()
+/// };
+/// let saved = save_controls(&controls);
+/// assert_eq!(load_controls(&saved).unwrap(), controls);
+/// ```
+pub fn save_controls(controls: &RtsCameraControls) -> String {
+    let registry = registry();
+    let serializer = TypedReflectSerializer::new(controls, &registry);
+    ron::ser::to_string_pretty(&serializer, ron::ser::PrettyConfig::default())
+        .expect("RtsCameraControls should always be serializable")
+}
+
+/// Loads `RtsCameraControls` from a RON string created by `save_controls`.
+pub fn load_controls(ron: &str) -> ron::Result<RtsCameraControls> {
+    let registry = registry();
+    let registration = RtsCameraControls::get_type_registration();
+    let mut deserializer = ron::Deserializer::from_str(ron).map_err(|err| err.code)?;
+    let reflected =
+        TypedReflectDeserializer::new(&registration, &registry).deserialize(&mut deserializer)?;
+    RtsCameraControls::from_reflect(&*reflected)
+        .ok_or_else(|| ron::Error::Message("invalid RtsCameraControls".to_string()))
+}
+
+fn registry() -> TypeRegistry {
+    let mut registry = TypeRegistry::default();
+    registry.register::<RtsCameraControls>();
+    registry
+}
@@ -0,0 +1,164 @@
+//! Helpers for driving an `RtsCamera` from integration tests without a real window. They send the
+//! same input events Bevy's own input systems read, so the camera reacts exactly as it would to a
+//! player. Each `step` advances time by a fixed amount, so results are the same on every run.
+//! # Example
+//! ```no_run
+//! # use std::time::Duration;
+//! # use bevy::prelude::*;
+//! # use bevy_rts_camera::{testing, RtsCamera, RtsCameraControls};
+//! let mut app = testing::app();
+//! let camera = testing::spawn_camera(&mut app, RtsCamera::default(), RtsCameraControls::default());
+//! testing::step(&mut app, Duration::from_millis(100));
+//! let before = testing::target_zoom(&app, camera);
+//! testing::scroll(&mut app, 1.0);
+//! testing::step(&mut app, Duration::from_millis(100));
+//! assert!(testing::target_zoom(&app, camera) > before);
+//! ```
+
+use std::time::Duration;
+
+use bevy::asset::Assets;
+use bevy::input::keyboard::{Key, KeyboardInput, NativeKey};
+use bevy::input::mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::input::ButtonState;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::{PrimaryWindow, WindowPlugin, WindowResolution};
+
+use crate::{RtsCamera, RtsCameraControls, RtsCameraPlugin};
+
+/// Creates a headless `App` with `RtsCameraPlugin` and the few Bevy plugins it needs, without a
+/// window or renderer.
+pub fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        InputPlugin,
+        TransformPlugin,
+        WindowPlugin {
+            primary_window: None,
+            ..default()
+        },
+        RtsCameraPlugin,
+    ))
+    // Needed for raycasting against `Ground`, which is done without `AssetPlugin` here
+    .init_resource::<Assets<Mesh>>();
+    app
+}
+
+/// Spawns a camera with `cam` and `controls`, returning its entity. The camera doesn't render
+/// anything, and has no viewport.
+pub fn spawn_camera(app: &mut App, cam: RtsCamera, controls: RtsCameraControls) -> Entity {
+    app.world_mut()
+        .spawn((
+            Camera::default(),
+            Projection::default(),
+            TransformBundle::default(),
+            cam,
+            controls,
+        ))
+        .id()
+}
+
+/// Spawns a primary window of `size` logical pixels, for the controls that use the cursor (e.g.
+/// edge panning). Nothing is actually opened. Use `move_cursor` to put the cursor in it.
+pub fn spawn_window(app: &mut App, size: Vec2) -> Entity {
+    app.world_mut()
+        .spawn((
+            Window {
+                resolution: WindowResolution::new(size.x, size.y),
+                ..default()
+            },
+            PrimaryWindow,
+        ))
+        .id()
+}
+
+/// Moves the cursor to `position` in logical pixels in the primary window (see `spawn_window`), or
+/// out of the window if `None`.
+pub fn move_cursor(app: &mut App, position: Option<Vec2>) {
+    let mut window_q = app
+        .world_mut()
+        .query_filtered::<&mut Window, With<PrimaryWindow>>();
+    let mut window = window_q
+        .get_single_mut(app.world_mut())
+        .expect("there should be a primary window");
+    window.set_cursor_position(position);
+}
+
+/// Sends a mouse wheel event of `lines` lines. Positive values zoom in.
+pub fn scroll(app: &mut App, lines: f32) {
+    app.world_mut().send_event(MouseWheel {
+        unit: MouseScrollUnit::Line,
+        x: 0.0,
+        y: lines,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+/// Sends a mouse motion event, as if the mouse was moved by `delta` pixels.
+pub fn move_mouse(app: &mut App, delta: Vec2) {
+    app.world_mut().send_event(MouseMotion { delta });
+}
+
+/// Sends a key press event. The key stays held until `release_key` is called.
+pub fn press_key(app: &mut App, key: KeyCode) {
+    send_key(app, key, ButtonState::Pressed);
+}
+
+/// Sends a key release event.
+pub fn release_key(app: &mut App, key: KeyCode) {
+    send_key(app, key, ButtonState::Released);
+}
+
+/// Sends a mouse button press event. The button stays held until `release_button` is called.
+pub fn press_button(app: &mut App, button: MouseButton) {
+    send_button(app, button, ButtonState::Pressed);
+}
+
+/// Sends a mouse button release event.
+pub fn release_button(app: &mut App, button: MouseButton) {
+    send_button(app, button, ButtonState::Released);
+}
+
+/// Runs the app's schedules once, so that any inputs sent since the last step are processed. Both
+/// real and virtual time advance by exactly `delta`, rather than by how long the step took.
+pub fn step(app: &mut App, delta: Duration) {
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(delta));
+    app.update();
+}
+
+/// Returns the `target_focus` of `camera`, which must have an `RtsCamera`.
+pub fn target_focus(app: &App, camera: Entity) -> Transform {
+    rts_camera(app, camera).target_focus
+}
+
+/// Returns the `target_zoom` of `camera`, which must have an `RtsCamera`.
+pub fn target_zoom(app: &App, camera: Entity) -> f32 {
+    rts_camera(app, camera).target_zoom
+}
+
+/// Returns the `RtsCamera` of `camera`, e.g. to check values other than its targets.
+pub fn rts_camera(app: &App, camera: Entity) -> &RtsCamera {
+    app.world()
+        .get::<RtsCamera>(camera)
+        .expect("entity should have an RtsCamera")
+}
+
+fn send_key(app: &mut App, key_code: KeyCode, state: ButtonState) {
+    app.world_mut().send_event(KeyboardInput {
+        key_code,
+        logical_key: Key::Unidentified(NativeKey::Unidentified),
+        state,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+fn send_button(app: &mut App, button: MouseButton, state: ButtonState) {
+    app.world_mut().send_event(MouseButtonInput {
+        button,
+        state,
+        window: Entity::PLACEHOLDER,
+    });
+}
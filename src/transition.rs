@@ -0,0 +1,137 @@
+use std::f32::consts::PI;
+
+use crate::{RtsCamera, RtsCameraSystemSet};
+use bevy::prelude::*;
+
+pub struct RtsCameraTransitionPlugin;
+
+impl Plugin for RtsCameraTransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<RtsCameraTransitionFinished>()
+            .add_systems(Update, transition.before(RtsCameraSystemSet));
+    }
+}
+
+/// Moves an `RtsCamera` from one camera's state to another's over a fixed amount of time, e.g.
+/// to hand off between two views. Add this to the camera entity that should move. The camera's
+/// targets will follow an eased path, and this component will be removed once it arrives (sending
+/// a `RtsCameraTransitionFinished` event).
+/// # Example
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_rts_camera::{RtsCamera, RtsCameraTransition};
+/// fn start_transition(mut commands: Commands, cam_q: Query<(Entity, &RtsCamera)>) {
+///     let mut cams = cam_q.iter();
+///     if let (Some((entity, from)), Some((_, to))) = (cams.next(), cams.next()) {
+///         commands
+///             .entity(entity)
+///             .insert(RtsCameraTransition::new(from, to, 2.0));
+///     }
+/// }
+/// ```
+#[derive(Component, Copy, Clone, Debug)]
+pub struct RtsCameraTransition {
+    /// The state to start from.
+    pub from: RtsCamera,
+    /// The state to end at.
+    pub to: RtsCamera,
+    /// How long the transition takes in seconds.
+    pub duration: f32,
+    /// How long the transition has been running in seconds.
+    pub elapsed: f32,
+    /// How the camera speeds up and slows down along the way.
+    /// Defaults to `RtsCameraEasing::EaseInOut`.
+    pub easing: RtsCameraEasing,
+}
+
+impl RtsCameraTransition {
+    /// Creates a transition between the targets of `from` and `to` lasting `duration` seconds.
+    pub fn new(from: &RtsCamera, to: &RtsCamera, duration: f32) -> Self {
+        RtsCameraTransition {
+            from: *from,
+            to: *to,
+            duration,
+            elapsed: 0.0,
+            easing: RtsCameraEasing::EaseInOut,
+        }
+    }
+
+    /// Sets how the camera speeds up and slows down along the way.
+    pub fn with_easing(mut self, easing: RtsCameraEasing) -> Self {
+        self.easing = easing;
+        self
+    }
+}
+
+/// How an animated camera move (such as `RtsCameraTransition`) progresses over time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+pub enum RtsCameraEasing {
+    /// Moves at a constant speed.
+    Linear,
+    /// Starts slowly, speeds up, then slows down into the end.
+    #[default]
+    EaseInOut,
+    /// Starts quickly, then slows down into the end.
+    EaseOut,
+    /// Starts quickly and overshoots the end slightly, before settling back onto it.
+    Spring,
+}
+
+impl RtsCameraEasing {
+    /// The eased progress at `t`, where `t` goes from `0.0` at the start to `1.0` at the end.
+    /// Always `0.0` at the start and `1.0` at the end, but may go past `1.0` in between for
+    /// `Spring`.
+    pub fn ease(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            RtsCameraEasing::Linear => t,
+            RtsCameraEasing::EaseInOut => t * t * (3.0 - 2.0 * t),
+            RtsCameraEasing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            // A decaying oscillation, scaled down towards the end so it lands exactly on `1.0`
+            RtsCameraEasing::Spring => 1.0 - (1.0 - t) * (-6.0 * t).exp() * (3.0 * PI * t).cos(),
+        }
+    }
+}
+
+/// Event sent when a `RtsCameraTransition` has finished.
+#[derive(Event, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RtsCameraTransitionFinished {
+    /// The camera entity that finished transitioning.
+    pub entity: Entity,
+}
+
+fn transition(
+    mut commands: Commands,
+    mut cam_q: Query<(Entity, &mut RtsCamera, &mut RtsCameraTransition)>,
+    mut finished: EventWriter<RtsCameraTransitionFinished>,
+    time: Res<Time<Real>>,
+) {
+    for (entity, mut cam, mut transition) in cam_q.iter_mut() {
+        transition.elapsed += time.delta_seconds();
+        let t = if transition.duration > 0.0 {
+            (transition.elapsed / transition.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        let t = transition.easing.ease(t);
+
+        let (from, to) = (&transition.from, &transition.to);
+        cam.target_focus.translation = from
+            .target_focus
+            .translation
+            .lerp(to.target_focus.translation, t);
+        cam.target_focus.rotation = from
+            .target_focus
+            .rotation
+            .slerp(to.target_focus.rotation, t);
+        cam.target_zoom = from.target_zoom.lerp(to.target_zoom, t);
+        cam.min_angle = from.min_angle.lerp(to.min_angle, t);
+        // Follow the path exactly, rather than lagging behind it
+        cam.reset_smoothing();
+
+        if transition.elapsed >= transition.duration {
+            commands.entity(entity).remove::<RtsCameraTransition>();
+            finished.send(RtsCameraTransitionFinished { entity });
+        }
+    }
+}
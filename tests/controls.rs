@@ -0,0 +1,915 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::window::WindowFocused;
+use bevy_mod_raycast::prelude::CursorRay;
+use bevy_rts_camera::{
+    testing, GroundHit, RtsCamera, RtsCameraControls, RtsCameraControlsSystemSet,
+    RtsCameraPanIntent, RtsCameraSmoothing, RtsCameraSystemSet, ZoomAnchor, ZoomCursorMiss,
+};
+
+const FRAME: Duration = Duration::from_millis(100);
+
+fn setup() -> (App, Entity) {
+    let mut app = testing::app();
+    let camera =
+        testing::spawn_camera(&mut app, RtsCamera::default(), RtsCameraControls::default());
+    testing::step(&mut app, FRAME);
+    (app, camera)
+}
+
+#[test]
+fn scroll_zooms_in() {
+    let (mut app, camera) = setup();
+    let before = testing::target_zoom(&app, camera);
+    testing::scroll(&mut app, 1.0);
+    testing::step(&mut app, FRAME);
+    assert!(testing::target_zoom(&app, camera) > before);
+}
+
+#[test]
+fn key_pan_is_deterministic() {
+    let pan = || {
+        let (mut app, camera) = setup();
+        testing::press_key(&mut app, KeyCode::ArrowUp);
+        for _ in 0..5 {
+            testing::step(&mut app, FRAME);
+        }
+        testing::target_focus(&app, camera).translation
+    };
+    let first = pan();
+    assert!(first.z < 0.0, "should pan forward, got {first}");
+    assert_eq!(first, pan());
+}
+
+#[test]
+fn rotate_is_applied_before_pan() {
+    let (mut app, camera) = setup();
+    testing::press_key(&mut app, KeyCode::KeyE);
+    testing::press_key(&mut app, KeyCode::ArrowUp);
+    testing::step(&mut app, FRAME);
+
+    // The keyboard rotates by `key_rotate_speed` over the width of the viewport (or 1280 pixels
+    // without a window) each frame, then pans in the new direction
+    let controls = RtsCameraControls::default();
+    let mut expected = RtsCamera::default();
+    controls.apply_rotate(
+        &mut expected,
+        std::f32::consts::PI * controls.key_rotate_speed / 1280.0,
+    );
+    controls.apply_pan(&mut expected, Vec2::Y, FRAME.as_secs_f32());
+
+    let focus = testing::target_focus(&app, camera);
+    assert!(
+        focus
+            .translation
+            .distance(expected.target_focus.translation)
+            < 1e-4,
+        "expected {}, got {}",
+        expected.target_focus.translation,
+        focus.translation
+    );
+}
+
+#[test]
+fn controls_can_be_removed_and_re_added() {
+    let (mut app, camera) = setup();
+    testing::press_key(&mut app, KeyCode::ArrowUp);
+    testing::step(&mut app, FRAME);
+    let moved = testing::target_focus(&app, camera);
+    assert!(moved.translation.z < 0.0);
+
+    // Without controls the targets are kept where they were
+    app.world_mut()
+        .entity_mut(camera)
+        .remove::<RtsCameraControls>();
+    testing::step(&mut app, FRAME);
+    assert_eq!(testing::target_focus(&app, camera), moved);
+
+    // Once re-added they carry on from there
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(RtsCameraControls::default());
+    testing::step(&mut app, FRAME);
+    let z = testing::target_focus(&app, camera).translation.z;
+    assert!(z < moved.translation.z, "should keep panning, got {z}");
+
+    // A grab pan in progress is held while they're gone, then carries on from the same grab
+    let dragged = grab_pan_across_churn(true);
+    assert!(dragged.z != 0.0, "should grab pan, got {dragged}");
+    assert_near(dragged, grab_pan_across_churn(false));
+}
+
+/// Where `target_focus` ends up after grab panning for three frames, with the controls removed
+/// for the middle one if `churn` is set. The mouse only moves while the controls are there.
+fn grab_pan_across_churn(churn: bool) -> Vec3 {
+    let mut app = testing::app();
+    testing::spawn_window(&mut app, Vec2::splat(1000.0));
+    // Towards a point on the ground in front of the focus, so the grab is further away than it
+    let ray = Ray3d::new(Vec3::new(0.0, 10.0, 0.0), Vec3::new(0.0, -1.0, -2.0));
+    app.add_systems(PreUpdate, move |mut cursor_ray: ResMut<CursorRay>| {
+        cursor_ray.0 = Some(ray);
+    });
+    let controls = RtsCameraControls {
+        button_drag: Some(MouseButton::Left),
+        lock_on_drag: true,
+        ..default()
+    };
+    let camera = testing::spawn_camera(&mut app, RtsCamera::default(), controls.clone());
+    testing::move_cursor(&mut app, MIDDLE);
+    testing::step(&mut app, FRAME);
+    testing::press_button(&mut app, MouseButton::Left);
+    testing::step(&mut app, FRAME);
+    testing::move_mouse(&mut app, Vec2::new(0.0, 50.0));
+    testing::step(&mut app, FRAME);
+    if churn {
+        app.world_mut()
+            .entity_mut(camera)
+            .remove::<RtsCameraControls>();
+        testing::move_mouse(&mut app, Vec2::new(0.0, 50.0));
+    }
+    testing::step(&mut app, FRAME);
+    if churn {
+        app.world_mut().entity_mut(camera).insert(controls);
+    }
+    testing::move_mouse(&mut app, Vec2::new(0.0, 50.0));
+    testing::step(&mut app, FRAME);
+    testing::target_focus(&app, camera).translation
+}
+
+#[test]
+fn interpolate_fixed_draws_between_fixed_steps() {
+    let mut app = testing::app();
+    app.insert_resource(Time::<Fixed>::from_seconds(0.1));
+    let camera = testing::spawn_camera(
+        &mut app,
+        RtsCamera {
+            focus_smoothing: 0.0,
+            zoom_smoothing: 0.0,
+            interpolate_fixed: true,
+            ..default()
+        },
+        RtsCameraControls::default(),
+    );
+    for _ in 0..4 {
+        testing::step(&mut app, FRAME / 2);
+    }
+    app.world_mut()
+        .get_mut::<RtsCamera>(camera)
+        .unwrap()
+        .target_focus
+        .translation
+        .x = 10.0;
+
+    let mut xs = Vec::new();
+    for _ in 0..6 {
+        testing::step(&mut app, FRAME / 2);
+        xs.push(app.world().get::<Transform>(camera).unwrap().translation.x);
+    }
+    // Half way between fixed steps, the camera is drawn half way between where they left it
+    assert!(
+        xs.iter().any(|x| (x - 5.0).abs() < 1e-4),
+        "should pass through 5.0, got {xs:?}"
+    );
+    assert!(xs.windows(2).all(|w| w[0] <= w[1]), "got {xs:?}");
+    assert_eq!(xs.last(), Some(&10.0));
+}
+
+#[test]
+fn interpolate_fixed_smooths_in_fixed_steps() {
+    // Where the focus is after a second of smoothing, at `frames` frames per second
+    let focus_at = |frames: u32| {
+        let mut app = testing::app();
+        app.insert_resource(Time::<Fixed>::from_seconds(0.1));
+        let camera = testing::spawn_camera(
+            &mut app,
+            RtsCamera {
+                interpolate_fixed: true,
+                ..default()
+            },
+            RtsCameraControls::default(),
+        );
+        testing::step(&mut app, FRAME);
+        app.world_mut()
+            .get_mut::<RtsCamera>(camera)
+            .unwrap()
+            .target_focus
+            .translation
+            .x = 10.0;
+        for _ in 0..frames {
+            testing::step(&mut app, Duration::from_secs(1) / frames);
+        }
+        testing::rts_camera(&app, camera).focus.translation.x
+    };
+    // The camera only moves on fixed steps, so the frame rate doesn't change where it ends up
+    let focus = focus_at(20);
+    assert!(
+        focus > 0.0 && focus < 10.0,
+        "should be part way, got {focus}"
+    );
+    assert_eq!(focus, focus_at(40));
+}
+
+/// Moves the targets of a camera with the given smoothing, and returns how far the focus and zoom
+/// still are from them after each of `frames` frames.
+fn remaining_after(cam: RtsCamera, frames: usize) -> Vec<(f32, f32)> {
+    let mut app = testing::app();
+    let camera = testing::spawn_camera(&mut app, cam, RtsCameraControls::default());
+    testing::step(&mut app, FRAME);
+    {
+        let mut cam = app.world_mut().get_mut::<RtsCamera>(camera).unwrap();
+        cam.target_focus.translation.x = 10.0;
+        cam.target_zoom = 1.0;
+    }
+    (0..frames)
+        .map(|_| {
+            testing::step(&mut app, FRAME);
+            let cam = testing::rts_camera(&app, camera);
+            (
+                cam.target_focus.translation.x - cam.focus.translation.x,
+                cam.target_zoom - cam.zoom,
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn focus_and_zoom_smoothing_converge_independently() {
+    // Snappy zoom, floaty focus
+    let remaining = remaining_after(
+        RtsCamera {
+            focus_smoothing: 0.9,
+            zoom_smoothing: 0.0,
+            ..default()
+        },
+        3,
+    );
+    assert!(remaining
+        .iter()
+        .all(|&(focus, zoom)| focus > 1.0 && zoom == 0.0));
+
+    // Floaty zoom, snappy focus
+    let remaining = remaining_after(
+        RtsCamera {
+            focus_smoothing: 0.0,
+            zoom_smoothing: 0.9,
+            ..default()
+        },
+        3,
+    );
+    assert!(remaining
+        .iter()
+        .all(|&(focus, zoom)| focus == 0.0 && zoom > 0.1));
+
+    // `set_smoothing` sets both, so they converge at the same rate
+    let mut cam = RtsCamera::default();
+    cam.set_smoothing(0.5);
+    for (focus, zoom) in remaining_after(cam, 10) {
+        assert!((focus / 10.0 - zoom).abs() < 1e-4, "{focus} vs {zoom}");
+    }
+}
+
+#[test]
+fn spring_zoom_respects_zoom_smoothing() {
+    let spring = RtsCameraSmoothing::Spring {
+        stiffness: 100.0,
+        damping: 20.0,
+    };
+    let remaining = remaining_after(
+        RtsCamera {
+            smoothing: spring,
+            focus_smoothing: 0.5,
+            zoom_smoothing: 0.1,
+            ..default()
+        },
+        2,
+    );
+    // The zoom spring runs faster than the focus spring, so has less of the way left to go
+    let (focus, zoom) = remaining[1];
+    assert!(zoom.abs() < focus.abs() / 10.0, "{focus} vs {zoom}");
+
+    let remaining = remaining_after(
+        RtsCamera {
+            smoothing: spring,
+            zoom_smoothing: 0.0,
+            ..default()
+        },
+        1,
+    );
+    assert_eq!(remaining[0].1, 0.0);
+}
+
+/// Whether each value is closer to zero than the last, without ever passing it.
+fn approaches_zero(values: impl IntoIterator<Item = f32>) -> bool {
+    let values: Vec<f32> = values.into_iter().collect();
+    values.iter().all(|v| *v >= 0.0) && values.windows(2).all(|w| w[1] <= w[0])
+}
+
+#[test]
+fn exponential_smoothing_never_overshoots_at_10_fps() {
+    // `FRAME` is a tenth of a second, so this runs at 10 frames per second
+    for smoothing in [0.0, 0.01, 0.3, 0.9] {
+        let mut cam = RtsCamera::default();
+        cam.set_smoothing(smoothing);
+        let remaining = remaining_after(cam, 20);
+        assert!(
+            approaches_zero(remaining.iter().map(|r| r.0)),
+            "focus with smoothing {smoothing}: {remaining:?}"
+        );
+        assert!(
+            approaches_zero(remaining.iter().map(|r| r.1)),
+            "zoom with smoothing {smoothing}: {remaining:?}"
+        );
+    }
+}
+
+#[test]
+fn zoom_accel_never_overshoots_at_10_fps() {
+    for zoom_accel in [0.5, 5.0, 1000.0] {
+        let remaining = remaining_after(
+            RtsCamera {
+                zoom_accel,
+                zoom_smoothing: 0.0,
+                ..default()
+            },
+            40,
+        );
+        assert!(
+            approaches_zero(remaining.iter().map(|r| r.1)),
+            "zoom_accel {zoom_accel}: {remaining:?}"
+        );
+        assert_eq!(remaining.last().unwrap().1, 0.0, "zoom_accel {zoom_accel}");
+    }
+}
+
+/// A camera with a 1000x1000 window for edge panning, and where it panned to after `frames` frames
+/// of holding `keys` with the cursor at `cursor`.
+fn pan_with(keys: &[KeyCode], cursor: Option<Vec2>, frames: usize) -> Vec3 {
+    drag_pan_with(RtsCameraControls::default(), keys, cursor, None, frames)
+}
+
+/// Like `pan_with`, but also drag panning with `button_drag` by `drag` pixels each frame.
+fn drag_pan_with(
+    controls: RtsCameraControls,
+    keys: &[KeyCode],
+    cursor: Option<Vec2>,
+    drag: Option<Vec2>,
+    frames: usize,
+) -> Vec3 {
+    let button_drag = controls.button_drag;
+    let mut app = testing::app();
+    testing::spawn_window(&mut app, Vec2::splat(1000.0));
+    let camera = testing::spawn_camera(&mut app, RtsCamera::default(), controls);
+    testing::step(&mut app, FRAME);
+    testing::move_cursor(&mut app, cursor);
+    for key in keys {
+        testing::press_key(&mut app, *key);
+    }
+    if drag.is_some() {
+        testing::press_button(&mut app, button_drag.unwrap());
+    }
+    for _ in 0..frames {
+        if let Some(drag) = drag {
+            testing::move_mouse(&mut app, drag);
+        }
+        testing::step(&mut app, FRAME);
+    }
+    testing::target_focus(&app, camera).translation
+}
+
+const LEFT_EDGE: Option<Vec2> = Some(Vec2::new(1.0, 500.0));
+const MIDDLE: Option<Vec2> = Some(Vec2::new(500.0, 500.0));
+
+fn assert_near(a: Vec3, b: Vec3) {
+    assert!(a.distance(b) < 1e-4, "expected {b}, got {a}");
+}
+
+#[test]
+fn keyboard_and_edge_pan_combine_like_two_keys() {
+    let keys = pan_with(&[KeyCode::ArrowUp, KeyCode::ArrowLeft], MIDDLE, 3);
+    let key_and_edge = pan_with(&[KeyCode::ArrowUp], LEFT_EDGE, 3);
+    assert!(
+        keys.x < 0.0 && keys.z < 0.0,
+        "should pan diagonally, got {keys}"
+    );
+    assert_near(key_and_edge, keys);
+
+    // Diagonals are no faster than panning straight
+    let up = pan_with(&[KeyCode::ArrowUp], MIDDLE, 3);
+    assert!((keys.length() - up.length()).abs() < 1e-4);
+
+    // Opposite directions cancel out
+    let opposite = pan_with(&[KeyCode::ArrowRight], LEFT_EDGE, 3);
+    assert_near(opposite, Vec3::ZERO);
+}
+
+#[test]
+fn pan_sources_combine_in_priority_order() {
+    let controls = RtsCameraControls {
+        button_drag: Some(MouseButton::Left),
+        ..default()
+    };
+    let up = &[KeyCode::ArrowUp];
+    let drag = Some(Vec2::new(50.0, 0.0));
+    let pan =
+        |keys: &[KeyCode], cursor, drag| drag_pan_with(controls.clone(), keys, cursor, drag, 1);
+
+    let drag_only = pan(&[], MIDDLE, drag);
+    let key_only = pan(up, MIDDLE, None);
+    // Edge and keyboard pan are combined before clamping to `pan_speed` (see above)
+    let edge_and_key = pan(&[KeyCode::ArrowUp, KeyCode::ArrowLeft], MIDDLE, None);
+    assert!(drag_only.x != 0.0 && key_only.z != 0.0);
+
+    // Drag pan suppresses edge pan
+    assert_near(pan(&[], LEFT_EDGE, drag), drag_only);
+    // Keyboard pan is added to drag pan
+    assert_near(pan(up, MIDDLE, drag), drag_only + key_only);
+    // Keyboard pan is added to edge pan
+    assert_near(pan(up, LEFT_EDGE, None), edge_and_key);
+    // All three at once is drag and keyboard pan
+    assert_near(pan(up, LEFT_EDGE, drag), drag_only + key_only);
+
+    // With `drag_exclusive`, dragging suppresses keyboard pan too
+    let exclusive = RtsCameraControls {
+        drag_exclusive: true,
+        ..controls.clone()
+    };
+    let pan =
+        |keys: &[KeyCode], cursor, drag| drag_pan_with(exclusive.clone(), keys, cursor, drag, 1);
+    assert_near(pan(up, MIDDLE, drag), drag_only);
+    assert_near(pan(up, LEFT_EDGE, drag), drag_only);
+    assert_near(pan(up, LEFT_EDGE, None), edge_and_key);
+}
+
+#[test]
+fn all_pan_inputs_at_once_are_clamped_to_pan_speed() {
+    let controls = RtsCameraControls {
+        key_pan_up: Some(KeyCode::KeyW),
+        key_pan_right: Some(KeyCode::KeyD),
+        ..default()
+    };
+    // Part way zoomed in, so the zoom scales the pan speed
+    let cam = RtsCamera {
+        zoom: 0.5,
+        target_zoom: 0.5,
+        ..default()
+    };
+    let mut app = testing::app();
+    testing::spawn_window(&mut app, Vec2::splat(1000.0));
+    let camera = testing::spawn_camera(&mut app, cam, controls.clone());
+    testing::step(&mut app, FRAME);
+    let start = testing::target_focus(&app, camera).translation;
+
+    // Every input that is combined by the `pan` system, each as hard as it can go
+    testing::move_cursor(&mut app, Some(Vec2::new(999.0, 1.0)));
+    for key in [
+        KeyCode::ArrowUp,
+        KeyCode::ArrowRight,
+        KeyCode::KeyW,
+        KeyCode::KeyD,
+    ] {
+        testing::press_key(&mut app, key);
+    }
+    app.world_mut()
+        .entity_mut(camera)
+        .insert(RtsCameraPanIntent(Vec3::new(10.0, 0.0, -10.0)));
+    app.world_mut()
+        .get_mut::<RtsCamera>(camera)
+        .unwrap()
+        .set_pan_input(Vec2::ONE);
+    testing::step(&mut app, FRAME);
+    let moved = testing::target_focus(&app, camera).translation - start;
+
+    // The furthest a single full speed input pans in one frame
+    let mut expected = cam;
+    controls.apply_pan(&mut expected, Vec2::Y, FRAME.as_secs_f32());
+    let max = expected.target_focus.translation.length();
+    assert!(
+        moved.x > 0.0 && moved.z < 0.0,
+        "should pan diagonally, got {moved}"
+    );
+    assert!(
+        (moved.length() - max).abs() < 1e-4,
+        "expected {max}, got {}",
+        moved.length()
+    );
+}
+
+/// Where `point` is on screen for `camera`, as a direction from the camera scaled so that its
+/// distance in front of the camera is `1.0`.
+fn screen_direction(app: &App, camera: Entity, point: Vec3) -> Vec2 {
+    let tfm = app.world().get::<Transform>(camera).unwrap();
+    let local = tfm.rotation.inverse() * (point - tfm.translation);
+    local.xy() / -local.z
+}
+
+#[test]
+fn zoom_anchor_stays_under_cursor_when_rotated() {
+    let rotation = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+    let mut cam = RtsCamera {
+        // The anchor is exact as long as the angle doesn't change while zooming
+        dynamic_angle: false,
+        focus: Transform::from_rotation(rotation),
+        target_focus: Transform::from_rotation(rotation),
+        ..default()
+    };
+    cam.set_smoothing(0.0);
+    let mut app = testing::app();
+    let camera = testing::spawn_camera(&mut app, cam, RtsCameraControls::default());
+    testing::step(&mut app, FRAME);
+
+    // A point on the ground that is off to one side of the focus, as well as in front of it
+    let anchor = Vec3::new(3.0, 0.0, 2.0);
+    let before = screen_direction(&app, camera, anchor);
+    let mut cam = app.world_mut().get_mut::<RtsCamera>(camera).unwrap();
+    RtsCameraControls::default().apply_zoom(&mut cam, 1.0, Some(anchor));
+    testing::step(&mut app, FRAME);
+
+    assert!(testing::target_zoom(&app, camera) > 0.0);
+    let after = screen_direction(&app, camera, anchor);
+    assert!(
+        before.distance(after) < 1e-4,
+        "expected {before}, got {after}"
+    );
+}
+
+#[test]
+fn extreme_pitch_stays_between_straight_down_and_the_horizon() {
+    let controls = RtsCameraControls {
+        key_pitch_up: Some(KeyCode::KeyR),
+        key_pitch_down: Some(KeyCode::KeyF),
+        key_pitch_speed: 100.0,
+        ..default()
+    };
+    let mut app = testing::app();
+    let camera = testing::spawn_camera(&mut app, RtsCamera::default(), controls);
+    testing::step(&mut app, FRAME);
+    // Misconfigured after spawning, so validation hasn't caught it
+    {
+        let mut cam = app.world_mut().get_mut::<RtsCamera>(camera).unwrap();
+        cam.min_angle = 5.0;
+        cam.max_angle = -3.0;
+    }
+
+    let assert_upright = |app: &App| {
+        let angle = testing::rts_camera(app, camera).angle;
+        assert!(
+            (0.0..std::f32::consts::FRAC_PI_2).contains(&angle),
+            "angle out of bounds: {angle}"
+        );
+        let tfm = app.world().get::<Transform>(camera).unwrap();
+        assert!(tfm.up().y >= 0.0, "up vector flipped: {:?}", tfm.up());
+        assert!(tfm.forward().y <= 0.0, "looking above the horizon");
+    };
+    for (press, release) in [
+        (KeyCode::KeyR, KeyCode::KeyF),
+        (KeyCode::KeyF, KeyCode::KeyR),
+    ] {
+        testing::release_key(&mut app, release);
+        testing::press_key(&mut app, press);
+        for _ in 0..20 {
+            testing::step(&mut app, FRAME);
+            assert_upright(&app);
+        }
+    }
+}
+
+/// Where `target_focus` ends up after zooming in once with the cursor pointing at the sky.
+fn zoom_at_sky(zoom_cursor_miss: ZoomCursorMiss) -> Vec3 {
+    let mut app = testing::app();
+    // Up and to the right, so it never hits the ground
+    let sky = Ray3d::new(Vec3::new(0.0, 10.0, 0.0), Vec3::new(1.0, 0.5, 0.0));
+    app.add_systems(PreUpdate, move |mut cursor_ray: ResMut<CursorRay>| {
+        cursor_ray.0 = Some(sky);
+    });
+    let controls = RtsCameraControls {
+        zoom_anchor: ZoomAnchor::Cursor,
+        zoom_cursor_miss,
+        ..default()
+    };
+    let camera = testing::spawn_camera(&mut app, RtsCamera::default(), controls);
+    testing::step(&mut app, FRAME);
+    testing::scroll(&mut app, 1.0);
+    testing::step(&mut app, FRAME);
+    assert!(testing::target_zoom(&app, camera) > 0.0);
+    testing::target_focus(&app, camera).translation
+}
+
+#[test]
+fn zoom_cursor_miss_focus_centered_zooms_to_the_focus() {
+    assert_eq!(zoom_at_sky(ZoomCursorMiss::FocusCentered), Vec3::ZERO);
+}
+
+#[test]
+fn zoom_cursor_miss_clamp_to_horizon_zooms_towards_the_cursor() {
+    let focus = zoom_at_sky(ZoomCursorMiss::ClampToHorizon { max_distance: 50.0 });
+    // Towards the point on the horizon in the cursor's direction, i.e. to the right
+    assert!(focus.x > 0.0 && focus.z.abs() < 1e-4, "got {focus}");
+}
+
+#[test]
+fn pitch_follows_the_smoothed_zoom() {
+    let mut app = testing::app();
+    let camera =
+        testing::spawn_camera(&mut app, RtsCamera::default(), RtsCameraControls::default());
+    testing::step(&mut app, FRAME);
+    let start = testing::rts_camera(&app, camera).angle;
+    app.world_mut()
+        .get_mut::<RtsCamera>(camera)
+        .unwrap()
+        .target_zoom = 1.0;
+
+    for _ in 0..5 {
+        testing::step(&mut app, FRAME);
+        let cam = testing::rts_camera(&app, camera);
+        assert!(cam.zoom > 0.0 && cam.zoom < 1.0, "should still be zooming");
+
+        // The same angle as a camera resting at this zoom, rather than the angle at `target_zoom`
+        let mut resting = testing::app();
+        let resting_camera = testing::spawn_camera(
+            &mut resting,
+            RtsCamera {
+                zoom: cam.zoom,
+                target_zoom: cam.zoom,
+                ..default()
+            },
+            RtsCameraControls::default(),
+        );
+        testing::step(&mut resting, FRAME);
+        let expected = testing::rts_camera(&resting, resting_camera).angle;
+        assert!(
+            (cam.angle - expected).abs() < 1e-5,
+            "expected {expected}, got {}",
+            cam.angle
+        );
+        assert!(cam.angle > start);
+    }
+}
+
+#[test]
+fn zoom_eases_into_zoom_max() {
+    // `remaining_after` zooms all the way in, to `zoom_max`
+    let zoom: Vec<f32> = remaining_after(RtsCamera::default(), 20)
+        .iter()
+        .map(|r| r.1)
+        .collect();
+    assert!(approaches_zero(zoom.iter().copied()), "{zoom:?}");
+    // Each step is smaller than the last, rather than snapping at the end
+    let steps: Vec<f32> = zoom.windows(2).map(|w| w[0] - w[1]).collect();
+    assert!(steps.windows(2).all(|w| w[1] < w[0]), "{steps:?}");
+    assert!(zoom[zoom.len() - 2] > 0.0);
+
+    // Springs are critically damped at the limits, so don't overshoot either
+    let spring = RtsCamera {
+        smoothing: RtsCameraSmoothing::Spring {
+            stiffness: 100.0,
+            damping: 5.0,
+        },
+        ..default()
+    };
+    let zoom: Vec<f32> = remaining_after(spring, 20).iter().map(|r| r.1).collect();
+    assert!(approaches_zero(zoom.iter().copied()), "{zoom:?}");
+}
+
+#[test]
+fn ortho_scale_tracks_target_zoom() {
+    let mut app = testing::app();
+    let mut cam = RtsCamera {
+        ortho_scale_min: 0.2,
+        ortho_scale_max: 2.0,
+        ..default()
+    };
+    cam.set_smoothing(0.0);
+    let camera = app
+        .world_mut()
+        .spawn((
+            Camera::default(),
+            Projection::Orthographic(OrthographicProjection::default()),
+            TransformBundle::default(),
+            cam,
+            RtsCameraControls::default(),
+        ))
+        .id();
+
+    let mut last_scale = f32::INFINITY;
+    for zoom in [0.0, 0.25, 0.5, 0.75, 1.0] {
+        app.world_mut()
+            .get_mut::<RtsCamera>(camera)
+            .unwrap()
+            .target_zoom = zoom;
+        testing::step(&mut app, FRAME);
+        let Projection::Orthographic(projection) = app.world().get::<Projection>(camera).unwrap()
+        else {
+            panic!("should still be orthographic");
+        };
+        let expected = 2.0f32.lerp(0.2, zoom);
+        assert!(
+            (projection.scale - expected).abs() < 1e-5,
+            "at zoom {zoom}, expected {expected}, got {}",
+            projection.scale
+        );
+        // Zooming in always shows less
+        assert!(projection.scale < last_scale);
+        last_scale = projection.scale;
+    }
+}
+
+#[derive(Resource)]
+struct Override(Vec3);
+
+#[test]
+fn targets_set_before_the_camera_systems_are_used_the_same_frame() {
+    let mut app = testing::app();
+    app.add_systems(
+        Update,
+        (|target: Option<Res<Override>>, mut cam_q: Query<&mut RtsCamera>| {
+            let Some(target) = target else {
+                return;
+            };
+            for mut cam in cam_q.iter_mut() {
+                cam.target_focus.translation = target.0;
+                cam.target_zoom = 0.5;
+            }
+        })
+        .after(RtsCameraControlsSystemSet)
+        .before(RtsCameraSystemSet),
+    );
+    let mut cam = RtsCamera::default();
+    cam.set_smoothing(0.0);
+    let camera = testing::spawn_camera(&mut app, cam, RtsCameraControls::default());
+    testing::step(&mut app, FRAME);
+
+    // Panning with the keyboard at the same time is overridden
+    testing::press_key(&mut app, KeyCode::ArrowUp);
+    app.insert_resource(Override(Vec3::new(5.0, 0.0, 3.0)));
+    testing::step(&mut app, FRAME);
+
+    let cam = testing::rts_camera(&app, camera);
+    assert_eq!(cam.focus.translation, Vec3::new(5.0, 0.0, 3.0));
+    assert_eq!(cam.zoom, 0.5);
+    // The transform has been updated to match too
+    let tfm = app.world().get::<Transform>(camera).unwrap();
+    assert_eq!(tfm.translation.x, 5.0);
+}
+
+#[test]
+fn rebinding_rotate_while_held_stops_rotating() {
+    let (mut app, camera) = setup();
+    let rotation = |app: &App| testing::target_focus(app, camera).rotation;
+    testing::press_button(&mut app, MouseButton::Middle);
+    testing::move_mouse(&mut app, Vec2::new(100.0, 0.0));
+    testing::step(&mut app, FRAME);
+    let rotated = rotation(&app);
+    assert_ne!(rotated, Quat::IDENTITY, "should rotate before rebinding");
+
+    app.world_mut()
+        .get_mut::<RtsCameraControls>(camera)
+        .unwrap()
+        .button_rotate = MouseButton::Right;
+    for _ in 0..3 {
+        testing::move_mouse(&mut app, Vec2::new(100.0, 0.0));
+        testing::step(&mut app, FRAME);
+        assert_eq!(rotation(&app), rotated, "the old button is still held");
+    }
+
+    // Until the new button is pressed
+    testing::press_button(&mut app, MouseButton::Right);
+    testing::move_mouse(&mut app, Vec2::new(100.0, 0.0));
+    testing::step(&mut app, FRAME);
+    assert_ne!(rotation(&app), rotated);
+}
+
+#[test]
+fn view_offset_is_relative_to_a_rotated_camera() {
+    let transform_with = |view_offset: Vec2| {
+        let rotation = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        let mut app = testing::app();
+        let camera = testing::spawn_camera(
+            &mut app,
+            RtsCamera {
+                focus: Transform::from_rotation(rotation),
+                target_focus: Transform::from_rotation(rotation),
+                view_offset,
+                ..default()
+            },
+            RtsCameraControls::default(),
+        );
+        testing::step(&mut app, FRAME);
+        *app.world().get::<Transform>(camera).unwrap()
+    };
+    let still = transform_with(Vec2::ZERO);
+    for (offset, local) in [(Vec2::X, Vec3::X), (Vec2::Y, Vec3::Y)] {
+        let shaken = transform_with(offset);
+        // Moved along the camera's own right or up, whatever its heading and angle
+        let shift = still.rotation.inverse() * (shaken.translation - still.translation);
+        assert!(
+            shift.distance(local) < 1e-4,
+            "expected {local}, got {shift}"
+        );
+        assert_eq!(shaken.rotation, still.rotation);
+    }
+}
+
+#[test]
+fn motion_buffered_during_focus_gain_does_not_rotate() {
+    let (mut app, camera) = setup();
+    let rotation = |app: &App| testing::target_focus(app, camera).rotation;
+    testing::press_button(&mut app, MouseButton::Middle);
+    testing::step(&mut app, FRAME);
+
+    // The jump some platforms report when the window regains focus
+    app.world_mut().send_event(WindowFocused {
+        window: Entity::PLACEHOLDER,
+        focused: true,
+    });
+    testing::move_mouse(&mut app, Vec2::new(500.0, 0.0));
+    testing::step(&mut app, FRAME);
+    assert_eq!(rotation(&app), Quat::IDENTITY);
+
+    // Motion after that rotates as normal
+    testing::move_mouse(&mut app, Vec2::new(100.0, 0.0));
+    testing::step(&mut app, FRAME);
+    assert_ne!(rotation(&app), Quat::IDENTITY);
+}
+
+#[derive(Resource, Default)]
+struct ChangedFrames(usize);
+
+#[test]
+fn idle_camera_is_not_changed_every_frame() {
+    let mut app = testing::app();
+    let mut cam = RtsCamera::default();
+    cam.set_smoothing(0.0);
+    testing::spawn_camera(&mut app, cam, RtsCameraControls::default());
+    app.init_resource::<ChangedFrames>().add_systems(
+        Last,
+        |cam_q: Query<(), Changed<RtsCamera>>, mut changed: ResMut<ChangedFrames>| {
+            if !cam_q.is_empty() {
+                changed.0 += 1;
+            }
+        },
+    );
+    // Let the camera settle first
+    for _ in 0..50 {
+        testing::step(&mut app, FRAME);
+    }
+    app.world_mut().resource_mut::<ChangedFrames>().0 = 0;
+    for _ in 0..10 {
+        testing::step(&mut app, FRAME);
+    }
+    assert_eq!(app.world().resource::<ChangedFrames>().0, 0);
+}
+
+/// Where the cursor ray hits the ground, overriding the real one while it's set.
+#[derive(Resource, Default)]
+struct CursorGround(Option<Vec3>);
+
+#[test]
+fn cursor_lean_at_the_bounds_edge_returns_once_removed() {
+    let mut app = testing::app();
+    // On the right edge of the default bounds
+    let edge = Transform::from_xyz(20.0, 0.0, 0.0);
+    let mut cam = RtsCamera {
+        focus: edge,
+        target_focus: edge,
+        ..default()
+    };
+    cam.set_smoothing(0.0);
+    let controls = RtsCameraControls {
+        cursor_lean: 0.5,
+        ..default()
+    };
+    let camera = testing::spawn_camera(&mut app, cam, controls);
+    app.init_resource::<CursorGround>().add_systems(
+        Update,
+        (move |mut cam_q: Query<&mut RtsCamera>, cursor: Res<CursorGround>| {
+            if let Some(position) = cursor.0 {
+                cam_q.get_mut(camera).unwrap().cursor_ground = Some(GroundHit {
+                    entity: camera,
+                    position,
+                });
+            }
+        })
+        .before(RtsCameraControlsSystemSet),
+    );
+    testing::step(&mut app, FRAME);
+    let unleaned = app.world().get::<Transform>(camera).unwrap().translation;
+
+    // Leaning past the edge moves the view, but not the focus
+    app.insert_resource(CursorGround(Some(Vec3::new(40.0, 0.0, 0.0))));
+    for _ in 0..5 {
+        testing::step(&mut app, FRAME);
+    }
+    let leaned = app.world().get::<Transform>(camera).unwrap().translation;
+    assert!(leaned.x > unleaned.x, "should lean right, got {leaned}");
+    assert_eq!(testing::target_focus(&app, camera), edge);
+
+    app.insert_resource(CursorGround(None));
+    for _ in 0..5 {
+        testing::step(&mut app, FRAME);
+    }
+    assert_eq!(testing::target_focus(&app, camera), edge);
+    assert_eq!(testing::rts_camera(&app, camera).lean, Vec2::ZERO);
+    let returned = app.world().get::<Transform>(camera).unwrap().translation;
+    assert_near(returned, unleaned);
+}